@@ -0,0 +1,87 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const FAT_MAGIC: u32 = 0xcafe_babe;
+const FAT_MAGIC_64: u32 = 0xcafe_babf;
+const MH_MAGIC: u32 = 0xfeed_face;
+const MH_MAGIC_64: u32 = 0xfeed_facf;
+
+/// CPU_TYPE_X86 / CPU_TYPE_ARM with the 64-bit ABI flag
+const CPU_TYPE_X86_64: u32 = 0x0100_0007;
+const CPU_TYPE_ARM64: u32 = 0x0100_000c;
+
+/// Enough of the executable to read a fat header with a handful of slices
+const HEADER_LEN: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    X86_64,
+    Arm64,
+    Other,
+}
+
+impl Arch {
+    fn from_cpu_type(cpu_type: u32) -> Self {
+        match cpu_type {
+            CPU_TYPE_X86_64 => Arch::X86_64,
+            CPU_TYPE_ARM64 => Arch::Arm64,
+            _ => Arch::Other,
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let word: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(word)
+    } else {
+        u32::from_le_bytes(word)
+    })
+}
+
+/// Architectures in a Mach-O header (universal or single-architecture)
+fn parse_header(bytes: &[u8]) -> Vec<Arch> {
+    let Some(magic) = read_u32(bytes, 0, true) else {
+        return Vec::new();
+    };
+
+    if magic == FAT_MAGIC || magic == FAT_MAGIC_64 {
+        let entry_len = if magic == FAT_MAGIC_64 { 32 } else { 20 };
+        let count = read_u32(bytes, 4, true).unwrap_or(0) as usize;
+        return (0..count)
+            .map_while(|i| read_u32(bytes, 8 + i * entry_len, true))
+            .map(Arch::from_cpu_type)
+            .collect();
+    }
+
+    // Thin binaries are little-endian on both Intel and Apple silicon
+    match read_u32(bytes, 0, false) {
+        Some(MH_MAGIC) | Some(MH_MAGIC_64) => read_u32(bytes, 4, false)
+            .map(|cpu_type| vec![Arch::from_cpu_type(cpu_type)])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Architectures the app's main executable is built for (empty if it can't be read)
+pub fn executable_archs(app_path: &Path) -> Vec<Arch> {
+    let info_plist = app_path.join("Contents/Info.plist");
+    let Some(executable) = plist::Value::from_file(&info_plist).ok().and_then(|v| {
+        v.as_dictionary()?
+            .get("CFBundleExecutable")?
+            .as_string()
+            .map(String::from)
+    }) else {
+        return Vec::new();
+    };
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    let read = File::open(app_path.join("Contents/MacOS").join(executable))
+        .and_then(|file| file.take(HEADER_LEN as u64).read_to_end(&mut header));
+    if read.is_err() {
+        return Vec::new();
+    }
+
+    parse_header(&header)
+}
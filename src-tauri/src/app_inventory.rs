@@ -0,0 +1,124 @@
+use crate::collation::NameCollator;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// What the watcher remembers about an installed bundle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InstalledApp {
+    pub bundle_id: String,
+    pub name: String,
+    pub path: String,
+    pub version: Option<String>,
+}
+
+/// A bundle that is still installed but now lives at another path
+#[derive(Debug, Clone, Serialize)]
+pub struct MovedBundle {
+    pub bundle_id: String,
+    pub name: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// A bundle whose version changed in place (or along with a move)
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdatedBundle {
+    pub bundle_id: String,
+    pub name: String,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
+
+/// Payload of the "apps-changed" event. Added and removed apps are sorted by name.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AppChanges {
+    pub added: Vec<InstalledApp>,
+    pub removed: Vec<InstalledApp>,
+    pub moved: Vec<MovedBundle>,
+    pub updated: Vec<UpdatedBundle>,
+}
+
+impl AppChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.updated.is_empty()
+    }
+}
+
+/// Installed apps per scanned root, so a change only rescans the roots it touched
+#[derive(Debug, Default)]
+pub struct AppInventory {
+    roots: Vec<(PathBuf, Vec<InstalledApp>)>,
+}
+
+impl AppInventory {
+    /// Replace what was found in a root (roots keep the order they were first added in)
+    pub fn set_root(&mut self, root: &Path, apps: Vec<InstalledApp>) {
+        match self.roots.iter_mut().find(|(r, _)| r == root) {
+            Some((_, existing)) => *existing = apps,
+            None => self.roots.push((root.to_path_buf(), apps)),
+        }
+    }
+
+    /// Installed apps by bundle ID; like the full scan, the first root that has a bundle wins
+    pub fn apps(&self) -> BTreeMap<String, InstalledApp> {
+        let mut apps = BTreeMap::new();
+        for app in self.roots.iter().flat_map(|(_, apps)| apps) {
+            apps.entry(app.bundle_id.clone())
+                .or_insert_with(|| app.clone());
+        }
+        apps
+    }
+}
+
+fn sort_by_name(apps: &mut [InstalledApp], collator: &NameCollator) {
+    apps.sort_by(|a, b| {
+        collator
+            .compare(&a.name, &b.name)
+            .then_with(|| a.bundle_id.cmp(&b.bundle_id))
+    });
+}
+
+/// What changed between two inventories
+pub fn diff(
+    before: &BTreeMap<String, InstalledApp>,
+    after: &BTreeMap<String, InstalledApp>,
+    collator: &NameCollator,
+) -> AppChanges {
+    let mut changes = AppChanges::default();
+
+    for (bundle_id, old) in before {
+        let Some(new) = after.get(bundle_id) else {
+            changes.removed.push(old.clone());
+            continue;
+        };
+        if old.path != new.path {
+            changes.moved.push(MovedBundle {
+                bundle_id: bundle_id.clone(),
+                name: new.name.clone(),
+                from: old.path.clone(),
+                to: new.path.clone(),
+            });
+        }
+        if old.version != new.version {
+            changes.updated.push(UpdatedBundle {
+                bundle_id: bundle_id.clone(),
+                name: new.name.clone(),
+                old_version: old.version.clone(),
+                new_version: new.version.clone(),
+            });
+        }
+    }
+    changes.added = after
+        .iter()
+        .filter(|(bundle_id, _)| !before.contains_key(*bundle_id))
+        .map(|(_, app)| app.clone())
+        .collect();
+
+    sort_by_name(&mut changes.added, collator);
+    sort_by_name(&mut changes.removed, collator);
+    changes
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 /// Watches application directories for changes and triggers a callback
-/// when apps are added, removed, or modified.
+/// with the changed paths when apps are added, removed, or modified.
 pub struct AppWatcher {
     debouncer: Option<Debouncer<notify::RecommendedWatcher>>,
     running: Arc<AtomicBool>,
@@ -17,7 +17,7 @@ impl AppWatcher {
     /// The callback is debounced - it will only fire after 1.5 seconds of no changes.
     pub fn new<F>(callback: F) -> Self
     where
-        F: Fn() + Send + Sync + 'static,
+        F: Fn(Vec<PathBuf>) + Send + Sync + 'static,
     {
         let running = Arc::new(AtomicBool::new(false));
         let running_clone = running.clone();
@@ -33,16 +33,20 @@ impl AppWatcher {
 
                 match result {
                     Ok(events) => {
-                        // Check if any events are related to .app bundles
-                        let has_app_changes = events.iter().any(|event| {
-                            // Check if the path or any parent is a .app bundle
-                            let path_str = event.path.to_string_lossy();
-                            path_str.contains(".app")
-                                && matches!(event.kind, DebouncedEventKind::Any | DebouncedEventKind::AnyContinuous)
-                        });
+                        // Keep the events related to .app bundles
+                        let app_paths: Vec<PathBuf> = events
+                            .into_iter()
+                            .filter(|event| {
+                                // Check if the path or any parent is a .app bundle
+                                let path_str = event.path.to_string_lossy();
+                                path_str.contains(".app")
+                                    && matches!(event.kind, DebouncedEventKind::Any | DebouncedEventKind::AnyContinuous)
+                            })
+                            .map(|event| event.path)
+                            .collect();
 
-                        if has_app_changes {
-                            callback();
+                        if !app_paths.is_empty() {
+                            callback(app_paths);
                         }
                     }
                     Err(error) => {
@@ -0,0 +1,193 @@
+use crate::collation::NameCollator;
+use crate::layout::{Folder, ItemRef, Layout, LayoutDiff};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+
+/// Tags with fewer apps than this go to the "Other" folder
+pub const DEFAULT_MIN_FOLDER_SIZE: usize = 3;
+
+const DEFAULT_OTHER_FOLDER_NAME: &str = "Other";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OrganizeOptions {
+    pub min_folder_size: usize,
+    /// Folder names by tag key (the frontend passes its translated labels)
+    pub folder_names: BTreeMap<String, String>,
+    pub other_folder_name: String,
+}
+
+impl Default for OrganizeOptions {
+    fn default() -> Self {
+        Self {
+            min_folder_size: DEFAULT_MIN_FOLDER_SIZE,
+            folder_names: BTreeMap::new(),
+            other_folder_name: DEFAULT_OTHER_FOLDER_NAME.to_string(),
+        }
+    }
+}
+
+/// An app to organize with its effective tags (most specific first)
+pub struct OrganizeApp {
+    pub bundle_id: String,
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposedFolder {
+    pub name: String,
+    /// None for the "Other" folder
+    pub tag: Option<String>,
+    pub apps: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrganizePreview {
+    pub folders: Vec<ProposedFolder>,
+    pub diff: LayoutDiff,
+    /// Whether the proposal was applied (false for a preview)
+    pub applied: bool,
+    /// Pass this back to apply the previewed proposal
+    pub token: String,
+}
+
+/// Identifies a proposal: the layout it was made from and the folders it fills.
+/// Folder IDs are left out, as new ones differ on every run.
+pub fn preview_token(current: &Layout, proposed: &Layout) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(current).unwrap_or_default());
+    for folder in &proposed.folders {
+        hasher.update(serde_json::to_vec(&(&folder.name, &folder.apps)).unwrap_or_default());
+    }
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// "dev-tools" -> "Dev Tools", for tags the frontend didn't name
+fn title_case(tag: &str) -> String {
+    tag.trim_start_matches("custom-")
+        .split('-')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A layout with one folder per tag (by each app's first tag), sorted by folder name.
+/// Smart folders keep their place and apps; `tag_labels` names custom tags.
+pub fn organize(
+    current: &Layout,
+    apps: &[OrganizeApp],
+    options: &OrganizeOptions,
+    tag_labels: &BTreeMap<String, String>,
+    collator: &NameCollator,
+) -> (Layout, Vec<ProposedFolder>) {
+    let min_size = options.min_folder_size.max(1);
+    let in_smart_folders: HashSet<&String> = current
+        .folders
+        .iter()
+        .filter(|f| f.rules.is_some())
+        .flat_map(|f| &f.apps)
+        .collect();
+
+    let mut apps: Vec<&OrganizeApp> = apps
+        .iter()
+        .filter(|a| !in_smart_folders.contains(&a.bundle_id))
+        .collect();
+    apps.sort_by(|a, b| collator.compare(&a.name, &b.name));
+
+    let mut groups: BTreeMap<&str, Vec<&OrganizeApp>> = BTreeMap::new();
+    let mut other: Vec<&OrganizeApp> = Vec::new();
+    for app in apps {
+        match app.tags.first() {
+            Some(tag) => groups.entry(tag).or_default().push(app),
+            None => other.push(app),
+        }
+    }
+
+    let mut named: Vec<(String, Option<String>, Vec<&OrganizeApp>)> = Vec::new();
+    for (tag, members) in groups {
+        if members.len() < min_size {
+            other.extend(members);
+            continue;
+        }
+        let name = options
+            .folder_names
+            .get(tag)
+            .or_else(|| tag_labels.get(tag))
+            .cloned()
+            .unwrap_or_else(|| title_case(tag));
+        named.push((name, Some(tag.to_string()), members));
+    }
+    named.sort_by(|a, b| collator.compare(&a.0, &b.0));
+    if !other.is_empty() {
+        other.sort_by(|a, b| collator.compare(&a.name, &b.name));
+        named.push((options.other_folder_name.clone(), None, other));
+    }
+
+    // Old folders stay until the end so new folder IDs never collide with reused ones
+    let mut layout = Layout {
+        pages: Vec::new(),
+        ..current.clone()
+    };
+    let smart: Vec<String> = current
+        .folders
+        .iter()
+        .filter(|f| f.rules.is_some())
+        .map(|f| f.id.clone())
+        .collect();
+    let mut kept: HashSet<String> = smart.iter().cloned().collect();
+
+    // Folders with a matching name keep their ID, so the diff shows them as changed, not replaced
+    let mut proposed = Vec::new();
+    for (name, tag, members) in named {
+        let existing = current
+            .folders
+            .iter()
+            .find(|f| {
+                f.rules.is_none()
+                    && !kept.contains(&f.id)
+                    && f.name.to_lowercase() == name.to_lowercase()
+            })
+            .map(|f| f.id.clone());
+        let folder = Folder {
+            id: existing.unwrap_or_else(|| layout.new_folder_id()),
+            name: name.clone(),
+            apps: members.iter().map(|a| a.bundle_id.clone()).collect(),
+            rules: None,
+        };
+        proposed.push(ProposedFolder {
+            name,
+            tag,
+            apps: folder.apps.len(),
+        });
+        layout.append(ItemRef::folder(&folder.id));
+        layout.folders.retain(|f| f.id != folder.id);
+        kept.insert(folder.id.clone());
+        layout.folders.push(folder);
+    }
+
+    // Smart folders go after the proposed ones
+    for id in smart {
+        layout.append(ItemRef::folder(&id));
+    }
+    layout.folders.retain(|f| kept.contains(&f.id));
+
+    // Apps that weren't organized (e.g. hidden ones) keep a place at the end
+    let placed: HashSet<String> = layout.all_bundle_ids().into_iter().collect();
+    for bundle_id in current.all_bundle_ids() {
+        if !placed.contains(&bundle_id) {
+            layout.append(ItemRef::app(&bundle_id));
+        }
+    }
+
+    layout.reflow();
+    (layout, proposed)
+}
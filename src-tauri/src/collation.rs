@@ -0,0 +1,38 @@
+use icu_collator::{Collator, CollatorOptions, Strength};
+use icu_locid::Locale;
+use std::cmp::Ordering;
+
+/// UI languages, matching the language picker in the frontend
+pub const SUPPORTED_LANGUAGES: &[&str] = &["en", "es", "fr", "de", "zh", "ja", "uk", "pl"];
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Orders app and folder names for a UI language: ignoring case and accents, with pinyin
+/// order for Chinese and kana order for Japanese. ICU has no way to know how a name in
+/// kanji is read, so those sort after all kana names in JIS order, not by their reading.
+pub struct NameCollator {
+    collator: Option<Collator>,
+}
+
+impl NameCollator {
+    pub fn for_language(language: &str) -> Self {
+        let locale: Locale = language.parse().unwrap_or_default();
+        let mut options = CollatorOptions::new();
+        options.strength = Some(Strength::Primary);
+
+        let collator = Collator::try_new(&(&locale).into(), options)
+            .map_err(|e| eprintln!("[Collation] No collation data for {}: {}", language, e))
+            .ok();
+        Self { collator }
+    }
+
+    /// Names that collate equal (e.g. "Édit" and "edit") fall back to code point order,
+    /// so sorting stays deterministic
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        let primary = match &self.collator {
+            Some(collator) => collator.compare(a, b),
+            None => a.to_lowercase().cmp(&b.to_lowercase()),
+        };
+        primary.then_with(|| a.cmp(b))
+    }
+}
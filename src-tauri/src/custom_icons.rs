@@ -0,0 +1,122 @@
+use crate::storage;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Icons the user picked live in <data dir>/icons as <content hash>.png. The layout only
+/// keeps the file name, so undo steps, snapshots and synced layouts stay small.
+const ICONS_DIR: &str = "icons";
+
+const DATA_URL_PREFIX: &str = "data:image/png;base64,";
+
+/// Icon references are file names we generated; anything else (e.g. from a synced or
+/// imported layout) must not be turned into a path
+fn is_reference(reference: &str) -> bool {
+    reference
+        .strip_suffix(".png")
+        .is_some_and(|hash| !hash.is_empty() && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn icons_dir() -> Result<PathBuf, String> {
+    let dir = storage::data_dir()
+        .ok_or("Failed to resolve app data directory")?
+        .join(ICONS_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// Path of a stored icon (None for anything that isn't an icon reference)
+pub fn icon_path(reference: &str) -> Option<PathBuf> {
+    if !is_reference(reference) {
+        return None;
+    }
+    icons_dir().ok().map(|dir| dir.join(reference))
+}
+
+/// Store PNG data and return the reference to keep in the layout
+pub fn store(png_data: &[u8]) -> Result<String, String> {
+    let hash = format!("{:x}", Sha256::digest(png_data));
+    let reference = format!("{}.png", &hash[..32]);
+    let path = icons_dir()?.join(&reference);
+    if !path.exists() {
+        fs::write(&path, png_data)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(reference)
+}
+
+/// PNG data URL of an icon. Layouts from before icons were stored as files still hold
+/// data URLs; those are returned as they are.
+pub fn data_url(icon: &str) -> Option<String> {
+    if icon.starts_with(DATA_URL_PREFIX) {
+        return Some(icon.to_string());
+    }
+    let png_data = fs::read(icon_path(icon)?).ok()?;
+    let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &png_data);
+    Some(format!("{}{}", DATA_URL_PREFIX, encoded))
+}
+
+/// Store an icon given as a data URL (layout files, older layouts) and return its reference
+fn store_data_url(url: &str) -> Result<String, String> {
+    let encoded = url
+        .strip_prefix(DATA_URL_PREFIX)
+        .ok_or("Custom icon is not a PNG data URL")?;
+    let png_data = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded)
+        .map_err(|e| format!("Invalid custom icon data: {}", e))?;
+    store(&png_data)
+}
+
+/// Replace data URLs with references to stored files. Icons that can't be stored are
+/// dropped. Returns whether anything changed.
+pub fn store_data_urls(icons: &mut BTreeMap<String, String>) -> bool {
+    let mut changed = false;
+    icons.retain(|bundle_id, icon| {
+        if is_reference(icon) {
+            return true;
+        }
+        changed = true;
+        match store_data_url(icon) {
+            Ok(reference) => {
+                *icon = reference;
+                true
+            }
+            Err(e) => {
+                eprintln!("[CustomIcons] Dropping icon of {}: {}", bundle_id, e);
+                false
+            }
+        }
+    });
+    changed
+}
+
+/// Data URLs for every icon, for files that leave this machine (layout export)
+pub fn resolve_all(icons: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    icons
+        .iter()
+        .filter_map(|(bundle_id, icon)| Some((bundle_id.clone(), data_url(icon)?)))
+        .collect()
+}
+
+/// Copy icon files between our icon dir and `dir` (a sync folder): references in `icons`
+/// that `dir` is missing are copied there with `to_dir`, otherwise missing ones are copied here
+pub fn copy_icons(
+    icons: &BTreeMap<String, String>,
+    dir: &Path,
+    to_dir: bool,
+) -> Result<(), String> {
+    let local_dir = icons_dir()?;
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for reference in icons.values().filter(|icon| is_reference(icon)) {
+        let (from, to) = if to_dir {
+            (local_dir.join(reference), dir.join(reference))
+        } else {
+            (dir.join(reference), local_dir.join(reference))
+        };
+        if !to.exists() && from.exists() {
+            fs::copy(&from, &to)
+                .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+        }
+    }
+    Ok(())
+}
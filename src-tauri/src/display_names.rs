@@ -0,0 +1,52 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const DISPLAY_NAMES_FILE: &str = "display_names.json";
+
+/// Longest name accepted for an override
+const MAX_NAME_LEN: usize = 100;
+
+/// Names the user gave apps, by bundle ID. The scanner shows them in place of
+/// CFBundleDisplayName/CFBundleName and keeps the bundle's name as the original.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DisplayNames {
+    #[serde(default)]
+    names: BTreeMap<String, String>,
+}
+
+impl DisplayNames {
+    /// Load the overrides from the app data dir (none if missing or unreadable)
+    pub fn load() -> Self {
+        storage::load_json(DISPLAY_NAMES_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(DISPLAY_NAMES_FILE, self)
+    }
+
+    pub fn get(&self, bundle_id: &str) -> Option<&String> {
+        self.names.get(bundle_id)
+    }
+
+    pub fn all(&self) -> &BTreeMap<String, String> {
+        &self.names
+    }
+
+    pub fn set(&mut self, bundle_id: &str, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Name cannot be empty".to_string());
+        }
+        if name.chars().count() > MAX_NAME_LEN {
+            return Err(format!("Name is longer than {} characters", MAX_NAME_LEN));
+        }
+        self.names.insert(bundle_id.to_string(), name.to_string());
+        Ok(())
+    }
+
+    /// Returns false if the app had no override
+    pub fn remove(&mut self, bundle_id: &str) -> bool {
+        self.names.remove(bundle_id).is_some()
+    }
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Result of seeding the first page from the Dock
+#[derive(Debug, Clone, Serialize)]
+pub struct DockSeedResult {
+    /// Bundle IDs placed at the start of page one, in Dock order
+    pub placed: Vec<String>,
+    /// Dock entries that don't match any scanned app
+    pub missing: Vec<DockApp>,
+}
+
+/// An app pinned in the Dock
+#[derive(Debug, Clone, Serialize)]
+pub struct DockApp {
+    pub label: Option<String>,
+    pub bundle_id: Option<String>,
+    pub path: Option<String>,
+}
+
+/// Location of the Dock preferences (~/Library/Preferences/com.apple.dock.plist)
+pub fn default_dock_plist_path() -> Option<PathBuf> {
+    let home_dir = std::env::var_os("HOME")?;
+    let path = PathBuf::from(home_dir).join("Library/Preferences/com.apple.dock.plist");
+    path.exists().then_some(path)
+}
+
+/// Read the `persistent-apps` entries of the Dock preferences, in Dock order
+pub fn read_dock_apps(plist_path: &Path) -> Result<Vec<DockApp>, String> {
+    let plist_value = plist::Value::from_file(plist_path)
+        .map_err(|e| format!("Failed to read Dock preferences: {}", e))?;
+    let plist_dict = plist_value
+        .as_dictionary()
+        .ok_or("Dock preferences are not a dictionary")?;
+
+    let entries = plist_dict
+        .get("persistent-apps")
+        .and_then(|v| v.as_array())
+        .map(|a| a.as_slice())
+        .unwrap_or_default();
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| entry.as_dictionary()?.get("tile-data")?.as_dictionary())
+        .map(|tile| {
+            let string_value = |key: &str| {
+                tile.get(key)
+                    .and_then(|v| v.as_string())
+                    .map(|s| s.to_string())
+            };
+
+            // file-data holds the bundle location as a file:// URL (_CFURLStringType 15)
+            // or, in older preferences, as a plain path (_CFURLStringType 0)
+            let path = tile
+                .get("file-data")
+                .and_then(|v| v.as_dictionary())
+                .and_then(|d| d.get("_CFURLString"))
+                .and_then(|v| v.as_string())
+                .map(file_url_to_path);
+
+            DockApp {
+                label: string_value("file-label"),
+                bundle_id: string_value("bundle-identifier"),
+                path,
+            }
+        })
+        .collect())
+}
+
+/// Resolve Dock entries to scanned apps by bundle ID, then by bundle path.
+/// `installed` holds (bundle ID, path) pairs.
+pub fn resolve_dock_apps(dock_apps: Vec<DockApp>, installed: &[(String, String)]) -> DockSeedResult {
+    let mut result = DockSeedResult {
+        placed: Vec::new(),
+        missing: Vec::new(),
+    };
+
+    for dock_app in dock_apps {
+        let found = installed
+            .iter()
+            .find(|(bundle_id, _)| {
+                dock_app
+                    .bundle_id
+                    .as_deref()
+                    .is_some_and(|id| id.eq_ignore_ascii_case(bundle_id))
+            })
+            .or_else(|| {
+                installed
+                    .iter()
+                    .find(|(_, path)| dock_app.path.as_deref() == Some(path.as_str()))
+            });
+
+        match found {
+            Some((bundle_id, _)) if !result.placed.contains(bundle_id) => {
+                result.placed.push(bundle_id.clone())
+            }
+            Some(_) => {}
+            None => result.missing.push(dock_app),
+        }
+    }
+
+    result
+}
+
+/// Convert "file:///Applications/Visual%20Studio%20Code.app/" to "/Applications/Visual Studio Code.app"
+fn file_url_to_path(url: &str) -> String {
+    let raw = url.strip_prefix("file://").unwrap_or(url);
+    let raw = raw.strip_prefix("localhost").unwrap_or(raw);
+
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    let path = String::from_utf8_lossy(&decoded).to_string();
+    if path.len() > 1 {
+        path.trim_end_matches('/').to_string()
+    } else {
+        path
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::Cursor;
+use std::path::Path;
+
+/// Extended attribute where Finder stores user tags
+const FINDER_TAGS_XATTR: &str = "com.apple.metadata:_kMDItemUserTags";
+
+/// A Finder tag as stored in the xattr: a name plus a label color index (0 = none, 1-7 = colors)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinderTag {
+    pub name: String,
+    pub color: u8,
+}
+
+impl FinderTag {
+    /// Parse a "Name\nColor" entry (the color part is optional)
+    fn parse(entry: &str) -> Option<Self> {
+        let (name, color) = match entry.rsplit_once('\n') {
+            Some((name, color)) => (name, color.trim().parse().unwrap_or(0)),
+            None => (entry, 0),
+        };
+
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name: name.to_string(),
+            color,
+        })
+    }
+
+    fn encode(&self) -> String {
+        format!("{}\n{}", self.name, self.color)
+    }
+}
+
+/// Read the Finder tags of a file or bundle (empty if it has none)
+pub fn read_finder_tags(path: &Path) -> Vec<FinderTag> {
+    let Ok(Some(data)) = xattr::get(path, FINDER_TAGS_XATTR) else {
+        return Vec::new();
+    };
+
+    // The xattr holds a binary plist array of strings
+    let Ok(value) = plist::Value::from_reader(Cursor::new(data)) else {
+        return Vec::new();
+    };
+
+    value
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|e| e.as_string())
+                .filter_map(FinderTag::parse)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace the Finder tags of a file or bundle
+pub fn write_finder_tags(path: &Path, tags: &[FinderTag]) -> Result<(), String> {
+    if tags.is_empty() {
+        // Nothing to remove
+        if !matches!(xattr::get(path, FINDER_TAGS_XATTR), Ok(Some(_))) {
+            return Ok(());
+        }
+        return xattr::remove(path, FINDER_TAGS_XATTR)
+            .map_err(|e| format!("Failed to clear Finder tags on {}: {}", path.display(), e));
+    }
+
+    let value = plist::Value::Array(
+        tags.iter()
+            .map(|t| plist::Value::String(t.encode()))
+            .collect(),
+    );
+
+    let mut data = Vec::new();
+    value
+        .to_writer_binary(&mut data)
+        .map_err(|e| format!("Failed to encode Finder tags: {}", e))?;
+
+    xattr::set(path, FINDER_TAGS_XATTR, &data)
+        .map_err(|e| format!("Failed to write Finder tags on {}: {}", path.display(), e))
+}
+
+/// Make the Launchpad-managed Finder tags of a bundle match a list of tag names and
+/// return the names it ends up with. Tags named in `managed` but missing from `names` are
+/// removed; any other tag (colors, tags Launchpad has no custom tag for) is left alone.
+/// Tags that stay keep their color, new ones are added without a color.
+pub fn sync_finder_tag_names(
+    path: &Path,
+    names: &[String],
+    managed: &[String],
+) -> Result<Vec<String>, String> {
+    let current = read_finder_tags(path);
+    let same = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+    let listed = |name: &str| names.iter().any(|n| same(n, name));
+    let is_managed = |name: &str| managed.iter().any(|m| same(m, name));
+
+    let mut tags: Vec<FinderTag> = current
+        .iter()
+        .filter(|t| listed(&t.name) || !is_managed(&t.name))
+        .cloned()
+        .collect();
+
+    for name in names {
+        if !tags.iter().any(|t| same(&t.name, name)) {
+            tags.push(FinderTag {
+                name: name.clone(),
+                color: 0,
+            });
+        }
+    }
+
+    if tags != current {
+        write_finder_tags(path, &tags)?;
+    }
+    Ok(tags.into_iter().map(|t| t.name).collect())
+}
@@ -0,0 +1,97 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+const HIDDEN_RULES_FILE: &str = "hidden_rules.json";
+
+/// Path patterns that hide every app they match. Unlike hidden bundle IDs (kept in the
+/// layout and synced with it), paths are machine-specific, so the rules stay local.
+///
+/// `*` matches within one path component, `**` across components, and a leading `~`
+/// is the home directory, e.g. `~/Applications/Chrome Apps.localized/**` or `**/*Helper*.app`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HiddenRules {
+    #[serde(default)]
+    patterns: BTreeSet<String>,
+}
+
+/// An app hidden from the grid and what hides it
+#[derive(Debug, Clone, Serialize)]
+pub struct HiddenApp {
+    pub bundle_id: String,
+    pub name: String,
+    pub path: String,
+    /// Whether the bundle ID itself is hidden
+    pub by_bundle_id: bool,
+    /// The first path rule that matches the app, if any
+    pub rule: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HiddenApps {
+    pub apps: Vec<HiddenApp>,
+    pub rules: Vec<String>,
+}
+
+fn expand_home(pattern: &str) -> String {
+    match (pattern.strip_prefix('~'), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}{}", home, rest),
+        _ => pattern.to_string(),
+    }
+}
+
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => (0..=path.len()).any(|i| glob_match(rest, &path[i..])),
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != '/')
+            .any(|i| glob_match(rest, &path[i..])),
+        [c, rest @ ..] => path.first() == Some(c) && glob_match(rest, &path[1..]),
+    }
+}
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = expand_home(pattern).chars().collect();
+    let path: Vec<char> = path.trim_end_matches('/').chars().collect();
+    glob_match(&pattern, &path)
+}
+
+impl HiddenRules {
+    /// Load the rules from the app data dir (no rules if missing or unreadable)
+    pub fn load() -> Self {
+        storage::load_json(HIDDEN_RULES_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(HIDDEN_RULES_FILE, self)
+    }
+
+    pub fn patterns(&self) -> Vec<String> {
+        self.patterns.iter().cloned().collect()
+    }
+
+    /// Add a rule; returns false if it was already there
+    pub fn add(&mut self, pattern: &str) -> Result<bool, String> {
+        let pattern = pattern.trim().trim_end_matches('/');
+        if pattern.is_empty() {
+            return Err("Pattern cannot be empty".to_string());
+        }
+        if !pattern.starts_with('/') && !pattern.starts_with('~') && !pattern.starts_with("**") {
+            return Err(format!(
+                "Pattern must start with /, ~ or **: \"{}\"",
+                pattern
+            ));
+        }
+        Ok(self.patterns.insert(pattern.to_string()))
+    }
+
+    pub fn remove(&mut self, pattern: &str) -> bool {
+        self.patterns.remove(pattern.trim().trim_end_matches('/'))
+    }
+
+    /// The first rule matching an app bundle path
+    pub fn matching_rule(&self, path: &str) -> Option<&String> {
+        self.patterns.iter().find(|pattern| matches(pattern, path))
+    }
+}
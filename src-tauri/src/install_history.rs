@@ -0,0 +1,178 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+const INSTALL_HISTORY_FILE: &str = "install_history.json";
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// When the scanner first saw a bundle and when its version last changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundleHistory {
+    /// Seconds since the Unix epoch; None for apps that were there on the first scan
+    #[serde(default)]
+    pub first_seen: Option<u64>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub previous_version: Option<String>,
+    #[serde(default)]
+    pub version_changed_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecentChange {
+    Installed,
+    Updated,
+}
+
+/// An install or update within the badge period
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentApp {
+    pub bundle_id: String,
+    pub change: RecentChange,
+    /// Seconds since the Unix epoch
+    pub at: u64,
+    pub version: Option<String>,
+    pub previous_version: Option<String>,
+    /// Whether the app was launched from Launchpad since (which clears its badge)
+    pub launched: bool,
+}
+
+/// Install and update times by bundle ID, kept across launches
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct InstallHistory {
+    /// False until the first scan has been recorded
+    #[serde(default)]
+    initialized: bool,
+    #[serde(default)]
+    apps: BTreeMap<String, BundleHistory>,
+}
+
+fn within(timestamp: u64, days: u64, now: u64) -> bool {
+    now.saturating_sub(timestamp) <= days * DAY_SECS
+}
+
+impl InstallHistory {
+    /// Load the history from the app data dir (empty if missing or unreadable)
+    pub fn load() -> Self {
+        storage::load_json(INSTALL_HISTORY_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(INSTALL_HISTORY_FILE, self)
+    }
+
+    /// Record scanned bundles and their versions. Apps found before the first full scan
+    /// (of every root) aren't treated as new. A full scan forgets bundles it didn't find,
+    /// so an app that is reinstalled counts as new again. Returns whether anything changed.
+    pub fn observe<'a, I>(&mut self, apps: I, full_scan: bool, now: u64) -> bool
+    where
+        I: IntoIterator<Item = (&'a str, Option<&'a str>)>,
+    {
+        let first_scan = !self.initialized;
+        let mut changed = first_scan && full_scan;
+        if full_scan {
+            self.initialized = true;
+        }
+
+        let mut seen = HashSet::new();
+        for (bundle_id, version) in apps {
+            seen.insert(bundle_id);
+            let version = version.map(String::from);
+            match self.apps.get_mut(bundle_id) {
+                Some(entry) => {
+                    if version.is_some() && entry.version != version {
+                        // A version appearing where there was none isn't an update
+                        if entry.version.is_some() {
+                            entry.previous_version = entry.version.take();
+                            entry.version_changed_at = Some(now);
+                        }
+                        entry.version = version;
+                        changed = true;
+                    }
+                }
+                None => {
+                    self.apps.insert(
+                        bundle_id.to_string(),
+                        BundleHistory {
+                            first_seen: (!first_scan).then_some(now),
+                            version,
+                            ..Default::default()
+                        },
+                    );
+                    changed = true;
+                }
+            }
+        }
+
+        if full_scan {
+            let before = self.apps.len();
+            self.apps
+                .retain(|bundle_id, _| seen.contains(bundle_id.as_str()));
+            changed |= self.apps.len() != before;
+        }
+        changed
+    }
+
+    /// Forget removed bundles, so they count as new if they come back.
+    /// Returns whether anything changed.
+    pub fn forget(&mut self, bundle_ids: &[String]) -> bool {
+        let before = self.apps.len();
+        self.apps
+            .retain(|bundle_id, _| !bundle_ids.contains(bundle_id));
+        self.apps.len() != before
+    }
+
+    /// (is_new, recently_updated) for an app: installed or updated within `days` days
+    /// and not launched since
+    pub fn badges(
+        &self,
+        bundle_id: &str,
+        last_launched: Option<u64>,
+        days: u64,
+        now: u64,
+    ) -> (bool, bool) {
+        let Some(entry) = self.apps.get(bundle_id) else {
+            return (false, false);
+        };
+        let fresh = |at: Option<u64>| {
+            at.is_some_and(|at| within(at, days, now) && last_launched.is_none_or(|l| l < at))
+        };
+        (fresh(entry.first_seen), fresh(entry.version_changed_at))
+    }
+
+    /// Installs and updates within `days` days, newest first
+    pub fn recent<F>(&self, days: u64, now: u64, last_launched: F) -> Vec<RecentApp>
+    where
+        F: Fn(&str) -> Option<u64>,
+    {
+        let mut recent = Vec::new();
+        for (bundle_id, entry) in &self.apps {
+            let launched_since = |at: u64| last_launched(bundle_id).is_some_and(|l| l >= at);
+            if let Some(at) = entry.first_seen.filter(|at| within(*at, days, now)) {
+                recent.push(RecentApp {
+                    bundle_id: bundle_id.clone(),
+                    change: RecentChange::Installed,
+                    at,
+                    version: entry.version.clone(),
+                    previous_version: None,
+                    launched: launched_since(at),
+                });
+            }
+            if let Some(at) = entry.version_changed_at.filter(|at| within(*at, days, now)) {
+                recent.push(RecentApp {
+                    bundle_id: bundle_id.clone(),
+                    change: RecentChange::Updated,
+                    at,
+                    version: entry.version.clone(),
+                    previous_version: entry.previous_version.clone(),
+                    launched: launched_since(at),
+                });
+            }
+        }
+        recent.sort_by(|a, b| b.at.cmp(&a.at).then_with(|| a.bundle_id.cmp(&b.bundle_id)));
+        recent
+    }
+}
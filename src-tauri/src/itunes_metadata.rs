@@ -0,0 +1,107 @@
+use image::imageops::FilterType;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Icons smaller than this (in pixels) are replaced by the App Store artwork when available
+pub const MIN_ICON_SIZE: u32 = 256;
+
+/// Size the App Store artwork is scaled down to
+const ARTWORK_ICON_SIZE: u32 = 256;
+
+/// App Store metadata shipped with iPhone/iPad apps installed on the Mac
+#[derive(Debug, Clone, Default)]
+pub struct ItunesMetadata {
+    pub genre: Option<String>,
+    pub artist: Option<String>,
+    pub artwork_path: Option<PathBuf>,
+}
+
+/// Read iTunesMetadata.plist from a wrapped iOS app (App.app/Wrapper/iTunesMetadata.plist)
+pub fn read_itunes_metadata(app_path: &Path) -> Option<ItunesMetadata> {
+    let wrapper_dir = app_path.join("Wrapper");
+    let metadata_path = [
+        wrapper_dir.join("iTunesMetadata.plist"),
+        app_path.join("iTunesMetadata.plist"),
+    ]
+    .into_iter()
+    .find(|p| p.exists())?;
+
+    let plist_value = plist::Value::from_file(&metadata_path).ok()?;
+    let plist_dict = plist_value.as_dictionary()?;
+
+    let string_value = |key: &str| {
+        plist_dict
+            .get(key)
+            .and_then(|v| v.as_string())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    // The artwork sits next to the metadata (no extension, PNG or JPEG inside)
+    let metadata_dir = metadata_path.parent().unwrap_or(app_path);
+    let artwork_path = ["iTunesArtwork@2x", "iTunesArtwork"]
+        .iter()
+        .map(|name| metadata_dir.join(name))
+        .find(|p| p.is_file());
+
+    Some(ItunesMetadata {
+        genre: string_value("genre"),
+        artist: string_value("artistName").or_else(|| string_value("playlistArtistName")),
+        artwork_path,
+    })
+}
+
+/// Map an App Store genre to our tags
+pub fn map_genre_to_tag(genre: &str) -> Option<&'static str> {
+    match genre {
+        "Developer Tools" => Some("dev-tools"),
+
+        "Social Networking" => Some("social"),
+
+        "Utilities" | "Navigation" | "Weather" => Some("utilities"),
+
+        "Games" | "Entertainment" | "Music" | "Sports" => Some("entertainment"),
+
+        "Photo & Video" | "Graphics & Design" => Some("creativity"),
+
+        "Productivity" | "Business" | "Finance" => Some("planning"),
+
+        "Education" | "Reference" | "Books" => Some("office"),
+
+        _ => None,
+    }
+}
+
+/// Size (width in pixels) of the largest PNG at the root of an iOS-style bundle
+pub fn largest_bundle_png_size(app_path: &Path) -> u32 {
+    let Ok(entries) = fs::read_dir(app_path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("png"))
+        .filter_map(|path| image::image_dimensions(&path).ok())
+        .map(|(width, _)| width)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Decode the App Store artwork and re-encode it as a PNG icon
+pub fn artwork_as_png(artwork_path: &Path) -> Option<Vec<u8>> {
+    // The file has no extension, so sniff the format from its contents
+    let artwork = image::ImageReader::open(artwork_path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .decode()
+        .ok()?;
+    let icon = artwork.resize(ARTWORK_ICON_SIZE, ARTWORK_ICON_SIZE, FilterType::Lanczos3);
+
+    let mut png_data = Vec::new();
+    icon.write_to(&mut Cursor::new(&mut png_data), image::ImageFormat::Png)
+        .ok()?;
+    Some(png_data)
+}
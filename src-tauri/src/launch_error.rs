@@ -0,0 +1,137 @@
+use serde::Serialize;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long to wait for `open` to hand the app to LaunchServices. Slower launches
+/// are assumed to have gone through, so the window isn't kept waiting.
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Why a launch failed, for the frontend to pick a localized message
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LaunchErrorKind {
+    /// The bundle was moved or deleted
+    MissingBundle,
+    /// The bundle is damaged, incomplete or blocked by Gatekeeper
+    Damaged,
+    /// macOS or a policy doesn't allow the app to run
+    NotPermitted,
+    /// The app needs a newer macOS or another architecture
+    Incompatible,
+    /// Bad launch options or an unknown preset
+    InvalidOptions,
+    Unknown,
+}
+
+/// A failed launch; `message` is `open`'s own error output, for logs and details
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchError {
+    pub kind: LaunchErrorKind,
+    pub app_path: String,
+    pub message: String,
+}
+
+impl LaunchError {
+    pub fn new(kind: LaunchErrorKind, app_path: &str, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            app_path: app_path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Map `open`'s error output (LaunchServices and OSStatus codes) to a failure kind
+fn classify(stderr: &str) -> LaunchErrorKind {
+    let text = stderr.to_lowercase();
+    let has_code = |codes: &[&str]| {
+        codes.iter().any(|code| {
+            text.match_indices(code).any(|(i, _)| {
+                // "-10810" shouldn't match "-108100"
+                !text[i + code.len()..].starts_with(|c: char| c.is_ascii_digit())
+            })
+        })
+    };
+
+    if text.contains("does not exist") || has_code(&["-43", "-10814"]) {
+        LaunchErrorKind::MissingBundle
+    } else if text.contains("not permitted")
+        || text.contains("permission denied")
+        || has_code(&["-54", "-10826"])
+    {
+        LaunchErrorKind::NotPermitted
+    } else if has_code(&["-10825", "-10661", "-10665"]) {
+        LaunchErrorKind::Incompatible
+    } else if text.contains("damaged") || has_code(&["-10810", "-10827", "-10673", "-67062"]) {
+        LaunchErrorKind::Damaged
+    } else {
+        LaunchErrorKind::Unknown
+    }
+}
+
+/// Run `open` with the given arguments for the app at `app_path` and report how it failed
+pub fn run_open(args: &[String], app_path: &str) -> Result<(), LaunchError> {
+    if !Path::new(app_path).exists() {
+        return Err(LaunchError::new(
+            LaunchErrorKind::MissingBundle,
+            app_path,
+            format!("The file {} does not exist.", app_path),
+        ));
+    }
+
+    let mut child = Command::new("open")
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            LaunchError::new(
+                LaunchErrorKind::Unknown,
+                app_path,
+                format!("Failed to run open: {}", e),
+            )
+        })?;
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() < LAUNCH_TIMEOUT => std::thread::sleep(POLL_INTERVAL),
+            Ok(None) => {
+                eprintln!(
+                    "[Launch] open is still running for {}, not waiting",
+                    app_path
+                );
+                std::thread::spawn(move || child.wait());
+                return Ok(());
+            }
+            Err(e) => {
+                return Err(LaunchError::new(
+                    LaunchErrorKind::Unknown,
+                    app_path,
+                    format!("Failed to wait for open: {}", e),
+                ))
+            }
+        }
+    };
+    if status.success() {
+        return Ok(());
+    }
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    let stderr = stderr.trim();
+    let message = if stderr.is_empty() {
+        format!("open exited with {}", status)
+    } else {
+        stderr.to_string()
+    };
+    eprintln!("[Launch] Failed to launch {}: {}", app_path, message);
+    Err(LaunchError::new(classify(&message), app_path, message))
+}
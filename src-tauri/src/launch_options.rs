@@ -0,0 +1,141 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const LAUNCH_PRESETS_FILE: &str = "launch_presets.json";
+
+/// How an app is opened, mapped onto the flags of `open`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchOptions {
+    /// Passed to the app after `--args`
+    pub args: Vec<String>,
+    /// Open a new instance even if the app is running (`-n`)
+    pub new_instance: bool,
+    /// Don't bring the app to the foreground (`-g`)
+    pub background: bool,
+    /// Launch the app hidden (`-j`)
+    pub hidden: bool,
+    /// Environment variables for the app (`--env`)
+    pub env: BTreeMap<String, String>,
+    /// Files or URLs to open with the app
+    pub files: Vec<String>,
+}
+
+/// Named launch options saved for one app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaunchPreset {
+    pub name: String,
+    pub options: LaunchOptions,
+}
+
+impl LaunchOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        for key in self.env.keys() {
+            let valid = !key.is_empty()
+                && !key.starts_with(|c: char| c.is_ascii_digit())
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(format!("Invalid environment variable name: \"{}\"", key));
+            }
+        }
+        if self.files.iter().any(|f| f.trim().is_empty()) {
+            return Err("File paths cannot be empty".to_string());
+        }
+        // `open` would take these for flags
+        if let Some(file) = self.files.iter().find(|f| f.starts_with('-')) {
+            return Err(format!("File paths cannot start with \"-\": \"{}\"", file));
+        }
+        Ok(())
+    }
+
+    /// Arguments for `open` that launch the app at `app_path` with these options
+    pub fn open_args(&self, app_path: &str) -> Vec<String> {
+        let mut open_args = Vec::new();
+        for (enabled, flag) in [
+            (self.new_instance, "-n"),
+            (self.background, "-g"),
+            (self.hidden, "-j"),
+        ] {
+            if enabled {
+                open_args.push(flag.to_string());
+            }
+        }
+        for (key, value) in &self.env {
+            open_args.push("--env".to_string());
+            open_args.push(format!("{}={}", key, value));
+        }
+        open_args.push("-a".to_string());
+        open_args.push(app_path.to_string());
+        open_args.extend(self.files.iter().cloned());
+        if !self.args.is_empty() {
+            open_args.push("--args".to_string());
+            open_args.extend(self.args.iter().cloned());
+        }
+        open_args
+    }
+}
+
+/// Launch presets by bundle ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LaunchPresets {
+    #[serde(default)]
+    apps: BTreeMap<String, Vec<LaunchPreset>>,
+}
+
+impl LaunchPresets {
+    /// Load the presets from the app data dir (none if missing or unreadable)
+    pub fn load() -> Self {
+        storage::load_json(LAUNCH_PRESETS_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(LAUNCH_PRESETS_FILE, self)
+    }
+
+    pub fn for_app(&self, bundle_id: &str) -> Vec<LaunchPreset> {
+        self.apps.get(bundle_id).cloned().unwrap_or_default()
+    }
+
+    pub fn get(&self, bundle_id: &str, name: &str) -> Option<&LaunchPreset> {
+        let name = name.trim();
+        self.apps.get(bundle_id)?.iter().find(|p| p.name == name)
+    }
+
+    /// Add a preset, or replace the app's preset with the same name
+    pub fn set(&mut self, bundle_id: &str, preset: LaunchPreset) -> Result<(), String> {
+        let name = preset.name.trim();
+        if name.is_empty() {
+            return Err("Preset name cannot be empty".to_string());
+        }
+        preset.options.validate()?;
+
+        let preset = LaunchPreset {
+            name: name.to_string(),
+            ..preset
+        };
+        let presets = self.apps.entry(bundle_id.to_string()).or_default();
+        match presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => presets.push(preset),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, bundle_id: &str, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        let presets = self
+            .apps
+            .get_mut(bundle_id)
+            .ok_or_else(|| format!("No launch presets for {}", bundle_id))?;
+        let before = presets.len();
+        presets.retain(|p| p.name != name);
+        if presets.len() == before {
+            return Err(format!("Launch preset not found: {}", name));
+        }
+        if presets.is_empty() {
+            self.apps.remove(bundle_id);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+use crate::layout::{Folder, ItemRef, Layout, Page};
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Root item of the Launchpad grid in the items table (other roots hold the Dashboard etc.)
+const ROOT_PAGE_UUID: &str = "ROOTPAGE";
+
+/// Name for Launchpad groups that never got a title
+const DEFAULT_FOLDER_TITLE: &str = "Folder";
+
+/// An installed app the imported entries can be matched against
+pub struct InstalledApp {
+    pub bundle_id: String,
+    pub name: String,
+}
+
+/// A Launchpad entry that has no matching app on this machine
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingApp {
+    pub title: String,
+    pub bundle_id: Option<String>,
+}
+
+/// Result of reading the system Launchpad database
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchpadImport {
+    pub layout: Layout,
+    pub matched: usize,
+    pub missing: Vec<MissingApp>,
+}
+
+/// One row of the items table, joined with its apps/groups details
+struct DbItem {
+    id: i64,
+    parent_id: i64,
+    uuid: Option<String>,
+    app: Option<(Option<String>, Option<String>)>, // (title, bundleid)
+    group_title: Option<String>,
+}
+
+/// Location of the Dock's Launchpad database:
+/// $(getconf DARWIN_USER_DIR)/com.apple.dock.launchpad/db/db
+pub fn default_db_path() -> Option<PathBuf> {
+    let output = Command::new("getconf").arg("DARWIN_USER_DIR").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let user_dir = String::from_utf8(output.stdout).ok()?;
+    let path = PathBuf::from(user_dir.trim()).join("com.apple.dock.launchpad/db/db");
+    path.exists().then_some(path)
+}
+
+/// Read the Launchpad database and rebuild its pages and folders as a layout
+pub fn import_launchpad_db(
+    db_path: &Path,
+    installed: &[InstalledApp],
+    page_size: usize,
+) -> Result<LaunchpadImport, String> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open Launchpad database: {}", e))?;
+
+    let items = read_items(&conn)?;
+
+    let mut children: HashMap<i64, Vec<&DbItem>> = HashMap::new();
+    for item in &items {
+        children.entry(item.parent_id).or_default().push(item);
+    }
+
+    let root = items
+        .iter()
+        .find(|i| i.uuid.as_deref() == Some(ROOT_PAGE_UUID))
+        .ok_or("Launchpad database has no root page")?;
+
+    let mut matcher = AppMatcher::new(installed);
+    let mut layout = Layout {
+        page_size: page_size.max(1),
+        ..Layout::default()
+    };
+
+    // Root -> pages -> apps and groups; groups -> pages -> apps
+    for page_item in children.get(&root.id).into_iter().flatten() {
+        let mut page = Page::default();
+
+        for entry in children.get(&page_item.id).into_iter().flatten() {
+            if let Some((title, bundle_id)) = &entry.app {
+                if let Some(matched) = matcher.resolve(title.as_deref(), bundle_id.as_deref()) {
+                    page.items.push(ItemRef::app(&matched));
+                }
+            } else if let Some(group_title) = &entry.group_title {
+                let apps: Vec<String> = children
+                    .get(&entry.id)
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|folder_page| children.get(&folder_page.id).into_iter().flatten())
+                    .filter_map(|folder_entry| folder_entry.app.as_ref())
+                    .filter_map(|(title, bundle_id)| {
+                        matcher.resolve(title.as_deref(), bundle_id.as_deref())
+                    })
+                    .collect();
+
+                // Folders whose apps are all missing here are dropped
+                if apps.is_empty() {
+                    continue;
+                }
+
+                let folder = Folder {
+                    id: layout.new_folder_id(),
+                    name: group_title.clone(),
+                    apps,
+                    rules: None,
+                };
+                page.items.push(ItemRef::folder(&folder.id));
+                layout.folders.push(folder);
+            }
+        }
+
+        if !page.items.is_empty() {
+            layout.pages.push(page);
+        }
+    }
+
+    // Apps installed since (or never shown in) Launchpad go at the end
+    let installed_ids: Vec<String> = installed.iter().map(|a| a.bundle_id.clone()).collect();
+    layout.sync_apps(&installed_ids);
+
+    Ok(LaunchpadImport {
+        layout,
+        matched: matcher.matched,
+        missing: matcher.missing,
+    })
+}
+
+fn read_items(conn: &Connection) -> Result<Vec<DbItem>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT items.rowid, items.parent_id, items.uuid,
+                    apps.item_id, apps.title, apps.bundleid,
+                    groups.item_id, groups.title
+             FROM items
+             LEFT JOIN apps ON apps.item_id = items.rowid
+             LEFT JOIN groups ON groups.item_id = items.rowid
+             ORDER BY items.parent_id, items.ordering",
+        )
+        .map_err(|e| format!("Unexpected Launchpad database schema: {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            let app_id: Option<i64> = row.get(3)?;
+            let group_id: Option<i64> = row.get(6)?;
+            Ok(DbItem {
+                id: row.get(0)?,
+                parent_id: row.get(1)?,
+                uuid: row.get(2)?,
+                app: match app_id {
+                    Some(_) => Some((row.get(4)?, row.get(5)?)),
+                    None => None,
+                },
+                group_title: match group_id {
+                    // Untitled groups still count as folders
+                    Some(_) => Some(
+                        row.get::<_, Option<String>>(7)?
+                            .unwrap_or_else(|| DEFAULT_FOLDER_TITLE.to_string()),
+                    ),
+                    None => None,
+                },
+            })
+        })
+        .map_err(|e| format!("Failed to read Launchpad database: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read Launchpad database: {}", e))
+}
+
+/// Matches Launchpad entries to scanned apps: by bundle ID, then by title
+/// (both case-insensitive). Each app is placed only once.
+struct AppMatcher<'a> {
+    by_id: HashMap<String, &'a InstalledApp>,
+    by_name: HashMap<String, &'a InstalledApp>,
+    placed: HashSet<String>,
+    matched: usize,
+    missing: Vec<MissingApp>,
+}
+
+impl<'a> AppMatcher<'a> {
+    fn new(installed: &'a [InstalledApp]) -> Self {
+        Self {
+            by_id: installed
+                .iter()
+                .map(|a| (a.bundle_id.to_lowercase(), a))
+                .collect(),
+            by_name: installed.iter().map(|a| (a.name.to_lowercase(), a)).collect(),
+            placed: HashSet::new(),
+            matched: 0,
+            missing: Vec::new(),
+        }
+    }
+
+    fn resolve(&mut self, title: Option<&str>, bundle_id: Option<&str>) -> Option<String> {
+        let found = bundle_id
+            .and_then(|id| self.by_id.get(&id.to_lowercase()))
+            .or_else(|| title.and_then(|t| self.by_name.get(&t.to_lowercase())))
+            .map(|app| app.bundle_id.clone());
+
+        match found {
+            Some(id) if self.placed.insert(id.clone()) => {
+                self.matched += 1;
+                Some(id)
+            }
+            // Duplicate entry for an app we already placed
+            Some(_) => None,
+            None => {
+                self.missing.push(MissingApp {
+                    title: title.unwrap_or_default().to_string(),
+                    bundle_id: bundle_id.map(|s| s.to_string()),
+                });
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Launchpad database with two pages: Safari, Notes (under an old bundle ID) and a
+    /// "Work" folder with Mail and a removed app; then a folder of removed apps and Terminal
+    fn create_db(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE items (rowid INTEGER PRIMARY KEY, uuid TEXT, parent_id INTEGER,
+                                 ordering INTEGER);
+             CREATE TABLE apps (item_id INTEGER, title TEXT, bundleid TEXT);
+             CREATE TABLE groups (item_id INTEGER, title TEXT);
+
+             INSERT INTO items VALUES (1, 'ROOTPAGE', 0, 0);
+             INSERT INTO items VALUES (2, 'page-1', 1, 0);
+             INSERT INTO items VALUES (3, 'page-2', 1, 1);
+
+             INSERT INTO items VALUES (10, NULL, 2, 0);
+             INSERT INTO apps VALUES (10, 'Safari', 'COM.APPLE.SAFARI');
+             INSERT INTO items VALUES (11, NULL, 2, 1);
+             INSERT INTO apps VALUES (11, 'Notes', 'com.apple.OldNotes');
+             INSERT INTO items VALUES (12, NULL, 2, 2);
+             INSERT INTO groups VALUES (12, 'Work');
+             INSERT INTO items VALUES (13, NULL, 12, 0);
+             INSERT INTO items VALUES (14, NULL, 13, 0);
+             INSERT INTO apps VALUES (14, 'Mail', 'com.apple.mail');
+             INSERT INTO items VALUES (15, NULL, 13, 1);
+             INSERT INTO apps VALUES (15, 'Gone', 'com.example.gone');
+
+             INSERT INTO items VALUES (20, NULL, 3, 0);
+             INSERT INTO groups VALUES (20, 'Old');
+             INSERT INTO items VALUES (21, NULL, 20, 0);
+             INSERT INTO items VALUES (22, NULL, 21, 0);
+             INSERT INTO apps VALUES (22, 'Retired', NULL);
+             INSERT INTO items VALUES (23, NULL, 3, 1);
+             INSERT INTO apps VALUES (23, 'Terminal', 'com.apple.Terminal');",
+        )
+        .unwrap();
+    }
+
+    fn installed(bundle_id: &str, name: &str) -> InstalledApp {
+        InstalledApp {
+            bundle_id: bundle_id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn rebuilds_pages_and_folders() {
+        let path = std::env::temp_dir().join(format!("launchpad-import-{}.db", std::process::id()));
+        create_db(&path);
+        let apps = [
+            installed("com.apple.Safari", "Safari"),
+            installed("com.apple.Notes", "Notes"),
+            installed("com.apple.mail", "Mail"),
+            installed("com.apple.Terminal", "Terminal"),
+            installed("com.apple.calculator", "Calculator"),
+        ];
+
+        let import = import_launchpad_db(&path, &apps, 10);
+        let _ = std::fs::remove_file(&path);
+        let import = import.unwrap();
+        let layout = &import.layout;
+
+        // Matched by bundle ID (ignoring case), then by title; the folder of removed apps
+        // is dropped
+        assert_eq!(layout.folders.len(), 1);
+        let folder = &layout.folders[0];
+        assert_eq!(folder.name, "Work");
+        assert_eq!(folder.apps, ["com.apple.mail"]);
+        assert_eq!(layout.pages.len(), 2);
+        assert_eq!(
+            layout.pages[0].items,
+            [
+                ItemRef::app("com.apple.Safari"),
+                ItemRef::app("com.apple.Notes"),
+                ItemRef::folder(&folder.id),
+            ]
+        );
+        // Apps Launchpad never showed go at the end
+        assert_eq!(
+            layout.pages[1].items,
+            [
+                ItemRef::app("com.apple.Terminal"),
+                ItemRef::app("com.apple.calculator"),
+            ]
+        );
+
+        assert_eq!(import.matched, 4);
+        let missing: Vec<(&str, Option<&str>)> = import
+            .missing
+            .iter()
+            .map(|m| (m.title.as_str(), m.bundle_id.as_deref()))
+            .collect();
+        assert_eq!(
+            missing,
+            [("Gone", Some("com.example.gone")), ("Retired", None)]
+        );
+    }
+}
@@ -0,0 +1,738 @@
+use crate::custom_icons;
+use crate::profiles::DEFAULT_PROFILE_ID;
+use crate::smart_folders::SmartRules;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Current layout file format version
+pub const LAYOUT_VERSION: u32 = 1;
+
+const LAYOUT_FILE: &str = "layout.json";
+
+/// Layout file of a profile (the default profile keeps the original file name)
+fn layout_file(profile_id: &str) -> String {
+    if profile_id == DEFAULT_PROFILE_ID {
+        LAYOUT_FILE.to_string()
+    } else {
+        format!("layout-{}.json", profile_id)
+    }
+}
+
+/// Items per page when nothing else is known (default 7x10 grid)
+pub const DEFAULT_PAGE_SIZE: usize = 70;
+
+/// Default name for folders created by dropping one app onto another
+const DEFAULT_FOLDER_NAME: &str = "Folder";
+
+/// A reference to something placed on a page
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ItemRef {
+    App { bundle_id: String },
+    Folder { id: String },
+}
+
+impl ItemRef {
+    pub fn app(bundle_id: &str) -> Self {
+        ItemRef::App {
+            bundle_id: bundle_id.to_string(),
+        }
+    }
+
+    pub fn folder(id: &str) -> Self {
+        ItemRef::Folder { id: id.to_string() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Folder {
+    pub id: String,
+    pub name: String,
+    /// Bundle IDs in display order
+    pub apps: Vec<String>,
+    /// Set for smart folders, whose apps are recomputed from the rules after every scan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<SmartRules>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Page {
+    pub items: Vec<ItemRef>,
+}
+
+/// The persisted arrangement of apps: pages of items, and the folders they reference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Layout {
+    pub version: u32,
+    /// Items per page; pages reflow when an insert overflows one
+    pub page_size: usize,
+    pub pages: Vec<Page>,
+    pub folders: Vec<Folder>,
+    /// Bundle IDs hidden from the grid (they keep their place in case they're shown again)
+    #[serde(default)]
+    pub hidden: BTreeSet<String>,
+    /// Pinned apps in display order, shown on every page or in their own row
+    /// (they keep their place on the grid too)
+    #[serde(default)]
+    pub favorites: Vec<String>,
+    /// Icons chosen by the user by bundle ID, as file names in the icon store (custom_icons)
+    #[serde(default)]
+    pub custom_icons: BTreeMap<String, String>,
+    /// Whether the old localStorage keys have been imported
+    #[serde(default)]
+    pub legacy_imported: bool,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            version: LAYOUT_VERSION,
+            page_size: DEFAULT_PAGE_SIZE,
+            pages: Vec::new(),
+            folders: Vec::new(),
+            hidden: BTreeSet::new(),
+            favorites: Vec::new(),
+            custom_icons: BTreeMap::new(),
+            legacy_imported: false,
+        }
+    }
+}
+
+/// Where an app sits in a layout
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppPosition {
+    pub page: usize,
+    pub index: usize,
+    /// Folder name and position inside it, for apps in folders
+    pub folder: Option<String>,
+    pub folder_index: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MovedApp {
+    pub bundle_id: String,
+    pub from: AppPosition,
+    pub to: AppPosition,
+}
+
+/// What changed between two layouts (apps by bundle ID, folders by name)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LayoutDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub moved: Vec<MovedApp>,
+    pub folders_added: Vec<String>,
+    pub folders_removed: Vec<String>,
+    /// (old name, new name)
+    pub folders_renamed: Vec<(String, String)>,
+}
+
+/// Bring an older layout file up to the current format
+fn migrate(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let version = value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32;
+
+    if version > LAYOUT_VERSION {
+        return Err(format!(
+            "Layout file version {} is newer than supported version {}",
+            version, LAYOUT_VERSION
+        ));
+    }
+
+    // Version 0: files written before the format was versioned
+    if version == 0 {
+        value["version"] = serde_json::json!(1);
+    }
+
+    Ok(value)
+}
+
+impl Layout {
+    /// Load a profile's layout from the app data dir (empty layout if missing or unreadable)
+    pub fn load(profile_id: &str) -> Self {
+        let Some(value) = storage::load_json::<serde_json::Value>(&layout_file(profile_id)) else {
+            return Self::default();
+        };
+
+        let loaded = migrate(value)
+            .and_then(|v| serde_json::from_value::<Self>(v).map_err(|e| e.to_string()));
+        match loaded {
+            Ok(mut layout) => {
+                // Older layouts kept custom icons inline as data URLs
+                if custom_icons::store_data_urls(&mut layout.custom_icons) {
+                    if let Err(e) = layout.save(profile_id) {
+                        eprintln!("[Layout] {}", e);
+                    }
+                }
+                layout
+            }
+            Err(e) => {
+                eprintln!("[Layout] Failed to load layout: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, profile_id: &str) -> Result<(), String> {
+        storage::save_json(&layout_file(profile_id), self)
+    }
+
+    /// Remove the layout file of a deleted profile
+    pub fn delete_file(profile_id: &str) -> Result<(), String> {
+        storage::remove_file(&layout_file(profile_id))
+    }
+
+    pub fn folder(&self, folder_id: &str) -> Option<&Folder> {
+        self.folders.iter().find(|f| f.id == folder_id)
+    }
+
+    fn folder_mut(&mut self, folder_id: &str) -> Result<&mut Folder, String> {
+        self.folders
+            .iter_mut()
+            .find(|f| f.id == folder_id)
+            .ok_or_else(|| format!("Unknown folder: {}", folder_id))
+    }
+
+    /// Folder containing an app, if any
+    pub fn folder_of(&self, bundle_id: &str) -> Option<&Folder> {
+        self.folders
+            .iter()
+            .find(|f| f.apps.iter().any(|a| a == bundle_id))
+    }
+
+    /// Page and index of a top-level item
+    pub fn locate(&self, item: &ItemRef) -> Option<(usize, usize)> {
+        self.pages.iter().enumerate().find_map(|(page_index, page)| {
+            page.items
+                .iter()
+                .position(|i| i == item)
+                .map(|index| (page_index, index))
+        })
+    }
+
+    /// All bundle IDs referenced by the layout, on pages and in folders
+    pub fn all_bundle_ids(&self) -> Vec<String> {
+        let mut ids = Vec::new();
+        for page in &self.pages {
+            for item in &page.items {
+                match item {
+                    ItemRef::App { bundle_id } => ids.push(bundle_id.clone()),
+                    ItemRef::Folder { id } => {
+                        if let Some(folder) = self.folder(id) {
+                            ids.extend(folder.apps.iter().cloned());
+                        }
+                    }
+                }
+            }
+        }
+        ids
+    }
+
+    /// Position of every app in the layout
+    pub fn positions(&self) -> HashMap<String, AppPosition> {
+        let mut positions = HashMap::new();
+        for (page_index, page) in self.pages.iter().enumerate() {
+            for (index, item) in page.items.iter().enumerate() {
+                match item {
+                    ItemRef::App { bundle_id } => {
+                        positions.insert(
+                            bundle_id.clone(),
+                            AppPosition {
+                                page: page_index,
+                                index,
+                                folder: None,
+                                folder_index: None,
+                            },
+                        );
+                    }
+                    ItemRef::Folder { id } => {
+                        let Some(folder) = self.folder(id) else {
+                            continue;
+                        };
+                        for (folder_index, bundle_id) in folder.apps.iter().enumerate() {
+                            positions.insert(
+                                bundle_id.clone(),
+                                AppPosition {
+                                    page: page_index,
+                                    index,
+                                    folder: Some(folder.name.clone()),
+                                    folder_index: Some(folder_index),
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        positions
+    }
+
+    /// Changes needed to get from this layout to `other`
+    pub fn diff(&self, other: &Layout) -> LayoutDiff {
+        let before = self.positions();
+        let after = other.positions();
+        let mut diff = LayoutDiff::default();
+
+        for bundle_id in other.all_bundle_ids() {
+            match (before.get(&bundle_id), after.get(&bundle_id)) {
+                (None, _) => diff.added.push(bundle_id),
+                (Some(from), Some(to)) if from != to => diff.moved.push(MovedApp {
+                    bundle_id,
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+                _ => {}
+            }
+        }
+        for bundle_id in self.all_bundle_ids() {
+            if !after.contains_key(&bundle_id) {
+                diff.removed.push(bundle_id);
+            }
+        }
+
+        for folder in &other.folders {
+            match self.folder(&folder.id) {
+                None => diff.folders_added.push(folder.name.clone()),
+                Some(old) if old.name != folder.name => diff
+                    .folders_renamed
+                    .push((old.name.clone(), folder.name.clone())),
+                _ => {}
+            }
+        }
+        for folder in &self.folders {
+            if other.folder(&folder.id).is_none() {
+                diff.folders_removed.push(folder.name.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Take an item out of wherever it is (a page, or a folder for apps).
+    /// A folder that loses its last app goes away with it.
+    fn detach(&mut self, item: &ItemRef) {
+        for page in &mut self.pages {
+            page.items.retain(|i| i != item);
+        }
+
+        if let ItemRef::App { bundle_id } = item {
+            let had_apps = self.folders_with_apps();
+            for folder in &mut self.folders {
+                folder.apps.retain(|a| a != bundle_id);
+            }
+            self.remove_emptied_folders(&had_apps);
+        }
+    }
+
+    /// Insert a top-level item at a page and index (clamped), creating pages as needed
+    fn insert(&mut self, item: ItemRef, page: usize, index: usize) {
+        while self.pages.len() <= page {
+            self.pages.push(Page::default());
+        }
+        let items = &mut self.pages[page].items;
+        let index = index.min(items.len());
+        items.insert(index, item);
+    }
+
+    /// Append a top-level item to the last page
+    pub fn append(&mut self, item: ItemRef) {
+        let page = self.pages.len().saturating_sub(1);
+        self.insert(item, page, usize::MAX);
+        self.reflow();
+    }
+
+    /// Push overflowing items to the next page and drop empty pages
+    pub fn reflow(&mut self) {
+        let page_size = self.page_size.max(1);
+        let mut page_index = 0;
+
+        while page_index < self.pages.len() {
+            if self.pages[page_index].items.len() > page_size {
+                let overflow = self.pages[page_index].items.split_off(page_size);
+                if page_index + 1 == self.pages.len() {
+                    self.pages.push(Page::default());
+                }
+                let next = &mut self.pages[page_index + 1].items;
+                next.splice(0..0, overflow);
+            }
+            page_index += 1;
+        }
+
+        self.pages.retain(|p| !p.items.is_empty());
+    }
+
+    /// Move a top-level item (or an app out of its folder) to a page and index
+    pub fn move_item(&mut self, item: ItemRef, page: usize, index: usize) -> Result<(), String> {
+        if let ItemRef::Folder { id } = &item {
+            self.folder_mut(id)?;
+        }
+
+        self.detach(&item);
+        self.insert(item, page, index);
+        self.reflow();
+        Ok(())
+    }
+
+    /// Move an app into a folder at an index (end of the folder if None)
+    pub fn move_to_folder(
+        &mut self,
+        bundle_id: &str,
+        folder_id: &str,
+        index: Option<usize>,
+    ) -> Result<(), String> {
+        let folder = self.folder_mut(folder_id)?;
+        // Moving within the folder must not empty (and so remove) it on the way
+        folder.apps.retain(|a| a != bundle_id);
+        self.detach(&ItemRef::app(bundle_id));
+
+        let folder = self.folder_mut(folder_id)?;
+        let index = index.unwrap_or(usize::MAX).min(folder.apps.len());
+        folder.apps.insert(index, bundle_id.to_string());
+
+        self.reflow();
+        Ok(())
+    }
+
+    /// Create a folder holding the given apps, placed where the first of them was
+    /// (an empty folder goes at the end and stays until it has had apps)
+    pub fn create_folder(&mut self, name: &str, bundle_ids: &[String]) -> Result<Folder, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Folder name cannot be empty".to_string());
+        }
+
+        let position = bundle_ids
+            .iter()
+            .find_map(|id| self.locate(&ItemRef::app(id)))
+            .or_else(|| {
+                bundle_ids
+                    .iter()
+                    .find_map(|id| self.folder_of(id))
+                    .and_then(|f| self.locate(&ItemRef::folder(&f.id)))
+            });
+
+        for bundle_id in bundle_ids {
+            self.detach(&ItemRef::app(bundle_id));
+        }
+
+        let folder = Folder {
+            id: self.new_folder_id(),
+            name: name.to_string(),
+            apps: bundle_ids.to_vec(),
+            rules: None,
+        };
+        self.folders.push(folder.clone());
+
+        match position {
+            Some((page, index)) => self.insert(ItemRef::folder(&folder.id), page, index),
+            None => self.append(ItemRef::folder(&folder.id)),
+        }
+
+        self.reflow();
+        Ok(folder)
+    }
+
+    pub fn rename_folder(&mut self, folder_id: &str, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Folder name cannot be empty".to_string());
+        }
+        self.folder_mut(folder_id)?.name = name.to_string();
+        Ok(())
+    }
+
+    /// Delete a folder, putting its apps back on the grid where the folder was
+    pub fn delete_folder(&mut self, folder_id: &str) -> Result<(), String> {
+        let folder = self.folder_mut(folder_id)?.clone();
+        let folder_ref = ItemRef::folder(folder_id);
+        let (page, index) = self
+            .locate(&folder_ref)
+            .unwrap_or((self.pages.len().saturating_sub(1), usize::MAX));
+
+        self.detach(&folder_ref);
+        self.folders.retain(|f| f.id != folder_id);
+
+        for (offset, bundle_id) in folder.apps.iter().enumerate() {
+            self.insert(ItemRef::app(bundle_id), page, index.saturating_add(offset));
+        }
+
+        self.reflow();
+        Ok(())
+    }
+
+    /// Drop one item onto another: app on app makes a new folder, anything on a folder
+    /// goes into it, a folder on an app takes the app in
+    pub fn merge(&mut self, source: &ItemRef, target: &ItemRef) -> Result<String, String> {
+        if source == target {
+            return Err("Cannot merge an item with itself".to_string());
+        }
+
+        match (source, target) {
+            (ItemRef::App { bundle_id: source_id }, ItemRef::App { bundle_id: target_id }) => {
+                let position = self.locate(target).or_else(|| {
+                    self.folder_of(target_id)
+                        .and_then(|f| self.locate(&ItemRef::folder(&f.id)))
+                });
+
+                self.detach(source);
+                self.detach(target);
+
+                let folder = Folder {
+                    id: self.new_folder_id(),
+                    name: DEFAULT_FOLDER_NAME.to_string(),
+                    apps: vec![target_id.clone(), source_id.clone()],
+                    rules: None,
+                };
+                let folder_id = folder.id.clone();
+                self.folders.push(folder);
+
+                match position {
+                    Some((page, index)) => self.insert(ItemRef::folder(&folder_id), page, index),
+                    None => self.append(ItemRef::folder(&folder_id)),
+                }
+
+                self.reflow();
+                Ok(folder_id)
+            }
+            (ItemRef::App { bundle_id }, ItemRef::Folder { id }) => {
+                self.move_to_folder(bundle_id, id, None)?;
+                Ok(id.clone())
+            }
+            (ItemRef::Folder { id }, ItemRef::App { bundle_id }) => {
+                // The folder takes the app's place
+                if let Some((page, index)) = self.locate(target) {
+                    self.detach(source);
+                    self.insert(source.clone(), page, index);
+                }
+                self.move_to_folder(bundle_id, id, None)?;
+                Ok(id.clone())
+            }
+            (ItemRef::Folder { id: source_id }, ItemRef::Folder { id: target_id }) => {
+                let source_apps = self.folder_mut(source_id)?.apps.clone();
+                self.folder_mut(target_id)?.apps.extend(source_apps);
+
+                self.detach(source);
+                self.folders.retain(|f| &f.id != source_id);
+                self.reflow();
+                Ok(target_id.clone())
+            }
+        }
+    }
+
+    /// Make the layout match the installed apps: append apps it doesn't know yet
+    /// and drop apps that are gone. Returns true if anything changed.
+    pub fn sync_apps(&mut self, installed: &[String]) -> bool {
+        let installed_set: HashSet<&String> = installed.iter().collect();
+        self.update_apps(|bundle_id| installed_set.contains(bundle_id), installed)
+    }
+
+    /// Drop removed apps and append newly installed ones in the given order, leaving the rest in place
+    pub fn apply_app_changes(&mut self, added: &[String], removed: &[String]) -> bool {
+        let removed_set: HashSet<&String> = removed.iter().collect();
+        self.update_apps(|bundle_id| !removed_set.contains(bundle_id), added)
+    }
+
+    /// Keep only the apps `keep` accepts, then append the `added` ones not placed yet.
+    /// Returns whether anything changed.
+    fn update_apps<F: Fn(&String) -> bool>(&mut self, keep: F, added: &[String]) -> bool {
+        let before = self.clone();
+        let had_apps = self.folders_with_apps();
+
+        for page in &mut self.pages {
+            page.items.retain(|item| match item {
+                ItemRef::App { bundle_id } => keep(bundle_id),
+                ItemRef::Folder { .. } => true,
+            });
+        }
+        for folder in &mut self.folders {
+            folder.apps.retain(|a| keep(a));
+        }
+        self.favorites.retain(|a| keep(a));
+        self.remove_emptied_folders(&had_apps);
+
+        // Folders that lost their place on a page still need one
+        let unplaced_folders: Vec<String> = self
+            .folders
+            .iter()
+            .filter(|f| self.locate(&ItemRef::folder(&f.id)).is_none())
+            .map(|f| f.id.clone())
+            .collect();
+        for folder_id in unplaced_folders {
+            self.append(ItemRef::folder(&folder_id));
+        }
+
+        let placed: HashSet<String> = self.all_bundle_ids().into_iter().collect();
+        for bundle_id in added {
+            if !placed.contains(bundle_id) {
+                self.append(ItemRef::app(bundle_id));
+            }
+        }
+
+        self.reflow();
+        *self != before
+    }
+
+    /// Put apps first on page one in the given order, taking them out of folders if needed
+    pub fn pin_to_first_page(&mut self, bundle_ids: &[String]) {
+        for bundle_id in bundle_ids.iter().rev() {
+            let item = ItemRef::app(bundle_id);
+            self.detach(&item);
+            self.insert(item, 0, 0);
+        }
+
+        self.reflow();
+    }
+
+    /// Pin an app to the favorites at an index (clamped; None appends), or move it there if pinned
+    pub fn pin_favorite(&mut self, bundle_id: &str, index: Option<usize>) -> Result<(), String> {
+        if !self.all_bundle_ids().iter().any(|id| id == bundle_id) {
+            return Err(format!("App not in layout: {}", bundle_id));
+        }
+
+        self.favorites.retain(|id| id != bundle_id);
+        let index = index.unwrap_or(usize::MAX).min(self.favorites.len());
+        self.favorites.insert(index, bundle_id.to_string());
+        Ok(())
+    }
+
+    pub fn unpin_favorite(&mut self, bundle_id: &str) -> Result<(), String> {
+        if !self.favorites.iter().any(|id| id == bundle_id) {
+            return Err(format!("App is not a favorite: {}", bundle_id));
+        }
+        self.favorites.retain(|id| id != bundle_id);
+        Ok(())
+    }
+
+    /// Put the favorites in a new order; it must list exactly the current favorites
+    pub fn reorder_favorites(&mut self, order: &[String]) -> Result<(), String> {
+        let current: HashSet<&String> = self.favorites.iter().collect();
+        let requested: HashSet<&String> = order.iter().collect();
+        if order.len() != self.favorites.len() || current != requested {
+            return Err("New order must contain exactly the current favorites".to_string());
+        }
+        self.favorites = order.to_vec();
+        Ok(())
+    }
+
+    /// Change the page size and reflow the pages
+    pub fn set_page_size(&mut self, page_size: usize) -> Result<(), String> {
+        if page_size == 0 {
+            return Err("Page size must be at least 1".to_string());
+        }
+        self.page_size = page_size;
+        self.reflow();
+        Ok(())
+    }
+
+    /// IDs of the folders that hold at least one app
+    fn folders_with_apps(&self) -> HashSet<String> {
+        self.folders
+            .iter()
+            .filter(|f| !f.apps.is_empty())
+            .map(|f| f.id.clone())
+            .collect()
+    }
+
+    /// Remove the folders that had apps (`had_apps`) and lost them all. Smart folders stay
+    /// even when nothing matches their rules, and so do folders created empty.
+    fn remove_emptied_folders(&mut self, had_apps: &HashSet<String>) {
+        let empty: Vec<String> = self
+            .folders
+            .iter()
+            .filter(|f| f.apps.is_empty() && f.rules.is_none() && had_apps.contains(&f.id))
+            .map(|f| f.id.clone())
+            .collect();
+
+        for folder_id in empty {
+            self.detach(&ItemRef::folder(&folder_id));
+            self.folders.retain(|f| f.id != folder_id);
+        }
+    }
+
+    /// New folder ID in the same format the frontend uses ("folder-<millis>")
+    pub fn new_folder_id(&self) -> String {
+        let millis = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+
+        let mut id = format!("folder-{}", millis);
+        let mut suffix = 1;
+        while self.folder(&id).is_some() {
+            id = format!("folder-{}-{}", millis, suffix);
+            suffix += 1;
+        }
+        id
+    }
+
+    /// One-time import of the `launchpad-folders` and `launchpad-item-order` localStorage keys.
+    /// `installed` is the alphabetical list of scanned bundle IDs, which the frontend used as
+    /// the base order before applying saved positions. Pages keep the layout's page size.
+    /// Returns false if already imported.
+    pub fn import_legacy(
+        &mut self,
+        folders: Vec<LegacyFolder>,
+        item_order: HashMap<String, i64>,
+        installed: &[String],
+    ) -> bool {
+        if self.legacy_imported {
+            return false;
+        }
+
+        let folders: Vec<Folder> = folders
+            .into_iter()
+            .map(|f| Folder {
+                id: f.id,
+                name: f.name,
+                apps: f.apps.into_iter().map(|a| a.bundle_id).collect(),
+                rules: None,
+            })
+            .collect();
+
+        // Same construction as mergeAppsAndFolders: standalone apps, then folders,
+        // then a stable sort by saved position (unknown items go last)
+        let in_folders: HashSet<&String> = folders.iter().flat_map(|f| f.apps.iter()).collect();
+        let mut items: Vec<ItemRef> = installed
+            .iter()
+            .filter(|id| !in_folders.contains(id))
+            .map(|id| ItemRef::app(id))
+            .chain(folders.iter().map(|f| ItemRef::folder(&f.id)))
+            .collect();
+
+        items.sort_by_key(|item| {
+            let id = match item {
+                ItemRef::App { bundle_id } => bundle_id,
+                ItemRef::Folder { id } => id,
+            };
+            item_order.get(id).copied().unwrap_or(9999)
+        });
+
+        self.folders = folders;
+        self.pages = items
+            .chunks(self.page_size.max(1))
+            .map(|chunk| Page {
+                items: chunk.to_vec(),
+            })
+            .collect();
+        self.sync_apps(installed);
+        self.legacy_imported = true;
+        true
+    }
+}
+
+/// Folder as the frontend stored it in localStorage (full App objects inside)
+#[derive(Debug, Deserialize)]
+pub struct LegacyFolder {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub apps: Vec<LegacyApp>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LegacyApp {
+    pub bundle_id: String,
+}
@@ -0,0 +1,299 @@
+use crate::layout::Layout;
+use crate::profiles::DEFAULT_PROFILE_ID;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Snapshots live in their own directory inside the app data dir, one file each
+const SNAPSHOT_DIR: &str = "snapshots";
+
+const UNDO_FILE: &str = "layout-undo.json";
+
+/// At most this many automatic snapshots are kept (manual ones stay until deleted)
+const MAX_AUTO_SNAPSHOTS: usize = 30;
+
+/// Automatic snapshots older than this are dropped
+const MAX_AUTO_SNAPSHOT_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How often the layout is snapshotted in the background (skipped if unchanged)
+pub const SCHEDULED_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Undo steps kept across restarts
+const MAX_UNDO_STEPS: usize = 50;
+
+/// The undo file is rewritten on every layout change, so the oldest steps are dropped
+/// once the saved layouts add up to more than this
+const MAX_UNDO_BYTES: usize = 2 * 1024 * 1024;
+
+/// Why a snapshot was taken
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnapshotReason {
+    Manual,
+    Scheduled,
+    BeforeDelete,
+    BeforeImport,
+    BeforeReset,
+    BeforeRestore,
+    BeforeOrganize,
+}
+
+impl SnapshotReason {
+    fn is_automatic(self) -> bool {
+        self != SnapshotReason::Manual
+    }
+}
+
+fn default_profile_id() -> String {
+    DEFAULT_PROFILE_ID.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    /// Profile whose layout this is (snapshots from before profiles belong to the default one)
+    #[serde(default = "default_profile_id")]
+    pub profile_id: String,
+    /// Seconds since the Unix epoch
+    pub created_at: u64,
+    pub reason: SnapshotReason,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub layout: Layout,
+}
+
+/// Snapshot summary for listing (without the layout itself)
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub created_at: u64,
+    pub reason: SnapshotReason,
+    pub label: Option<String>,
+    pub pages: usize,
+    pub folders: usize,
+    pub apps: usize,
+}
+
+impl From<&Snapshot> for SnapshotInfo {
+    fn from(snapshot: &Snapshot) -> Self {
+        Self {
+            id: snapshot.id.clone(),
+            created_at: snapshot.created_at,
+            reason: snapshot.reason,
+            label: snapshot.label.clone(),
+            pages: snapshot.layout.pages.len(),
+            folders: snapshot.layout.folders.len(),
+            apps: snapshot.layout.all_bundle_ids().len(),
+        }
+    }
+}
+
+fn snapshot_dir() -> Result<PathBuf, String> {
+    let dir = storage::data_dir()
+        .ok_or("Failed to resolve app data directory")?
+        .join(SNAPSHOT_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// Snapshot IDs are millisecond timestamps; anything else never names a snapshot file
+fn snapshot_path(id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Unknown snapshot: {}", id));
+    }
+    Ok(snapshot_dir()?.join(format!("{}.json", id)))
+}
+
+/// All readable snapshots of a profile, oldest first
+fn load_all(profile_id: &str) -> Result<Vec<Snapshot>, String> {
+    let dir = snapshot_dir()?;
+    let entries =
+        fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let mut snapshots: Vec<Snapshot> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|path| {
+            let contents = fs::read_to_string(&path).ok()?;
+            match serde_json::from_str(&contents) {
+                Ok(snapshot) => Some(snapshot),
+                Err(e) => {
+                    eprintln!("[LayoutHistory] Skipping {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .filter(|snapshot: &Snapshot| snapshot.profile_id == profile_id)
+        .collect();
+
+    snapshots.sort_by_key(|s: &Snapshot| s.id.parse::<u128>().unwrap_or(0));
+    Ok(snapshots)
+}
+
+/// Save a snapshot of a profile's layout. Automatic snapshots are skipped (None) when
+/// the layout hasn't changed since the profile's newest snapshot.
+pub fn create_snapshot(
+    profile_id: &str,
+    layout: &Layout,
+    reason: SnapshotReason,
+    label: Option<String>,
+) -> Result<Option<SnapshotInfo>, String> {
+    if reason.is_automatic() {
+        if let Some(latest) = load_all(profile_id)?.last() {
+            if latest.layout == *layout {
+                return Ok(None);
+            }
+        }
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    let mut millis = now.as_millis();
+    while snapshot_path(&millis.to_string())?.exists() {
+        millis += 1;
+    }
+
+    let snapshot = Snapshot {
+        id: millis.to_string(),
+        profile_id: profile_id.to_string(),
+        created_at: now.as_secs(),
+        reason,
+        label: label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+        layout: layout.clone(),
+    };
+    storage::write_json_atomic(&snapshot_path(&snapshot.id)?, &snapshot)?;
+
+    prune(profile_id)?;
+    Ok(Some(SnapshotInfo::from(&snapshot)))
+}
+
+/// Snapshot summaries of a profile, newest first
+pub fn list_snapshots(profile_id: &str) -> Result<Vec<SnapshotInfo>, String> {
+    Ok(load_all(profile_id)?
+        .iter()
+        .rev()
+        .map(SnapshotInfo::from)
+        .collect())
+}
+
+/// Load a snapshot of a profile; snapshots of other profiles are reported as unknown
+pub fn load_snapshot(profile_id: &str, id: &str) -> Result<Snapshot, String> {
+    let path = snapshot_path(id)?;
+    let contents =
+        fs::read_to_string(&path).map_err(|_| format!("Unknown snapshot: {}", id))?;
+    let snapshot: Snapshot = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to read snapshot {}: {}", id, e))?;
+    if snapshot.profile_id != profile_id {
+        return Err(format!("Unknown snapshot: {}", id));
+    }
+    Ok(snapshot)
+}
+
+pub fn delete_snapshot(profile_id: &str, id: &str) -> Result<(), String> {
+    load_snapshot(profile_id, id)?;
+    remove_snapshot_file(id)
+}
+
+fn remove_snapshot_file(id: &str) -> Result<(), String> {
+    let path = snapshot_path(id)?;
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete snapshot {}: {}", id, e))
+}
+
+/// Apply the retention limits to a profile's automatic snapshots
+fn prune(profile_id: &str) -> Result<(), String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let automatic: Vec<Snapshot> = load_all(profile_id)?
+        .into_iter()
+        .rev()
+        .filter(|s| s.reason.is_automatic())
+        .collect();
+
+    for (index, snapshot) in automatic.iter().enumerate() {
+        let age = now.saturating_sub(snapshot.created_at);
+        if index >= MAX_AUTO_SNAPSHOTS || age > MAX_AUTO_SNAPSHOT_AGE.as_secs() {
+            remove_snapshot_file(&snapshot.id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of steps available in each direction
+#[derive(Debug, Clone, Serialize)]
+pub struct UndoState {
+    pub undo_steps: usize,
+    pub redo_steps: usize,
+}
+
+/// Previous layouts for undo/redo, persisted so they survive restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UndoHistory {
+    #[serde(default)]
+    undo: Vec<Layout>,
+    #[serde(default)]
+    redo: Vec<Layout>,
+}
+
+impl UndoHistory {
+    pub fn load() -> Self {
+        storage::load_json(UNDO_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(UNDO_FILE, self)
+    }
+
+    /// Remember the layout as it was before a change; a new change clears the redo steps
+    pub fn record(&mut self, previous: Layout) {
+        self.undo.push(previous);
+        if self.undo.len() > MAX_UNDO_STEPS {
+            let excess = self.undo.len() - MAX_UNDO_STEPS;
+            self.undo.drain(..excess);
+        }
+        self.redo.clear();
+
+        // The newest step always stays, however big it is
+        let sizes: Vec<usize> = self
+            .undo
+            .iter()
+            .map(|layout| serde_json::to_vec(layout).map_or(0, |json| json.len()))
+            .collect();
+        let mut total: usize = sizes.iter().sum();
+        let mut excess = 0;
+        while total > MAX_UNDO_BYTES && excess + 1 < sizes.len() {
+            total -= sizes[excess];
+            excess += 1;
+        }
+        self.undo.drain(..excess);
+    }
+
+    /// Step back: returns the layout to show, keeping `current` for redo
+    pub fn undo(&mut self, current: Layout) -> Option<Layout> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// Step forward again after an undo
+    pub fn redo(&mut self, current: Layout) -> Option<Layout> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn state(&self) -> UndoState {
+        UndoState {
+            undo_steps: self.undo.len(),
+            redo_steps: self.redo.len(),
+        }
+    }
+}
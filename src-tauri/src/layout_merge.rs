@@ -0,0 +1,521 @@
+use crate::layout::{Folder, ItemRef, Layout, Page};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Where a top-level item or an app sits: on a page, or inside a folder
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Slot {
+    Page(usize),
+    Folder(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictKind {
+    /// Both sides moved the item to different places
+    Placement,
+    /// Both sides renamed the folder differently
+    FolderName,
+    /// One side deleted a folder the other side changed
+    FolderDeleted,
+    /// Both sides changed the favorites differently
+    Favorites,
+}
+
+/// A change both sides made differently. The local side always wins; this is only reported.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeConflict {
+    pub kind: ConflictKind,
+    /// Bundle ID or folder name (empty for favorites)
+    pub subject: String,
+}
+
+/// Three-way pick: take whichever side changed, local if both changed differently.
+/// Returns the value and whether it was a conflict.
+fn pick<T: PartialEq + Clone>(base: &T, local: &T, remote: &T) -> (T, bool) {
+    if local == base || local == remote {
+        (remote.clone(), false)
+    } else if remote == base {
+        (local.clone(), false)
+    } else {
+        (local.clone(), true)
+    }
+}
+
+fn slots(layout: &Layout) -> HashMap<ItemRef, Slot> {
+    let mut slots = HashMap::new();
+    for (page_index, page) in layout.pages.iter().enumerate() {
+        for item in &page.items {
+            slots.insert(item.clone(), Slot::Page(page_index));
+            if let ItemRef::Folder { id } = item {
+                for bundle_id in layout
+                    .folder(id)
+                    .map(|f| f.apps.as_slice())
+                    .unwrap_or_default()
+                {
+                    slots.insert(ItemRef::app(bundle_id), Slot::Folder(id.clone()));
+                }
+            }
+        }
+    }
+    slots
+}
+
+fn contents(layout: &Layout, slot: &Slot) -> Vec<ItemRef> {
+    match slot {
+        Slot::Page(index) => layout
+            .pages
+            .get(*index)
+            .map(|p| p.items.clone())
+            .unwrap_or_default(),
+        Slot::Folder(id) => layout
+            .folder(id)
+            .map(|f| f.apps.iter().map(|a| ItemRef::app(a)).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Merge the changes `remote` made since `base` into `local`.
+/// Only apps known locally are placed, so apps missing on either machine never move around.
+pub fn merge_layouts(
+    base: &Layout,
+    local: &Layout,
+    remote: &Layout,
+) -> (Layout, Vec<MergeConflict>) {
+    let mut conflicts = Vec::new();
+
+    // Folders: existence and name
+    let mut folder_ids: Vec<&String> = local.folders.iter().map(|f| &f.id).collect();
+    folder_ids.extend(
+        remote
+            .folders
+            .iter()
+            .map(|f| &f.id)
+            .filter(|id| local.folder(id).is_none()),
+    );
+
+    let mut folders: Vec<Folder> = Vec::new();
+    // Folders deleted remotely that we keep because we changed them
+    let mut kept_deleted: HashSet<String> = HashSet::new();
+    for id in folder_ids {
+        let (b, l, r) = (base.folder(id), local.folder(id), remote.folder(id));
+        let kept = match (b, l, r) {
+            (Some(b), Some(l), Some(r)) => {
+                let (name, conflict) = pick(&b.name, &l.name, &r.name);
+                if conflict {
+                    conflicts.push(MergeConflict {
+                        kind: ConflictKind::FolderName,
+                        subject: l.name.clone(),
+                    });
+                }
+                let (rules, _) = pick(&b.rules, &l.rules, &r.rules);
+                Some((name, rules))
+            }
+            (_, Some(l), None) if b.is_some() => {
+                // Deleted remotely: follow unless we changed it since
+                if b != Some(l) {
+                    conflicts.push(MergeConflict {
+                        kind: ConflictKind::FolderDeleted,
+                        subject: l.name.clone(),
+                    });
+                    kept_deleted.insert(id.clone());
+                    Some((l.name.clone(), l.rules.clone()))
+                } else {
+                    None
+                }
+            }
+            (Some(b), None, Some(r)) => {
+                // Deleted locally and changed remotely: stays deleted
+                if b != r {
+                    conflicts.push(MergeConflict {
+                        kind: ConflictKind::FolderDeleted,
+                        subject: b.name.clone(),
+                    });
+                }
+                None
+            }
+            (_, Some(l), _) => Some((l.name.clone(), l.rules.clone())),
+            (None, None, Some(r)) => Some((r.name.clone(), r.rules.clone())),
+            _ => None,
+        };
+
+        if let Some((name, rules)) = kept {
+            folders.push(Folder {
+                id: id.clone(),
+                name,
+                apps: Vec::new(),
+                rules,
+            });
+        }
+    }
+    let folder_set: HashSet<String> = folders.iter().map(|f| f.id.clone()).collect();
+
+    // Where every item ends up
+    let (base_slots, local_slots, remote_slots) = (slots(base), slots(local), slots(remote));
+    let local_apps: HashSet<String> = local.all_bundle_ids().into_iter().collect();
+
+    let mut items: Vec<&ItemRef> = local_slots.keys().collect();
+    items.extend(remote_slots.keys().filter(|item| {
+        !local_slots.contains_key(*item)
+            && match item {
+                ItemRef::App { bundle_id } => local_apps.contains(bundle_id),
+                ItemRef::Folder { id } => folder_set.contains(id),
+            }
+    }));
+    items.sort();
+
+    let valid = |item: &ItemRef, slot: &Option<Slot>| match (item, slot) {
+        (_, None) => false,
+        (ItemRef::Folder { id }, Some(Slot::Page(_))) => folder_set.contains(id),
+        (ItemRef::Folder { .. }, Some(Slot::Folder(_))) => false,
+        (ItemRef::App { .. }, Some(Slot::Page(_))) => true,
+        (ItemRef::App { .. }, Some(Slot::Folder(id))) => folder_set.contains(id),
+    };
+
+    let mut placement: BTreeMap<Slot, Vec<ItemRef>> = BTreeMap::new();
+    for item in items {
+        let b = base_slots.get(item).cloned();
+        let l = local_slots.get(item).cloned();
+        let r = remote_slots.get(item).cloned();
+
+        // An item the remote side doesn't have (e.g. not installed there) keeps its local place
+        // Apps of a folder we kept although the remote side deleted it stay in it
+        let in_kept_folder =
+            matches!(&l, Some(Slot::Folder(id)) if kept_deleted.contains(id)) && l != r;
+        let (mut slot, conflict) = if r.is_none() || in_kept_folder {
+            (l.clone(), false)
+        } else {
+            pick(&b, &l, &r)
+        };
+        if conflict {
+            conflicts.push(MergeConflict {
+                kind: ConflictKind::Placement,
+                subject: match item {
+                    ItemRef::App { bundle_id } => bundle_id.clone(),
+                    ItemRef::Folder { id } => local
+                        .folder(id)
+                        .map(|f| f.name.clone())
+                        .unwrap_or_else(|| id.clone()),
+                },
+            });
+        }
+
+        if !valid(item, &slot) {
+            slot = if valid(item, &l) { l } else { None };
+        }
+        let slot = match slot {
+            Some(slot) => slot,
+            // Its folder is gone: back onto the last page
+            None if matches!(item, ItemRef::App { .. }) => {
+                Slot::Page(local.pages.len().saturating_sub(1))
+            }
+            None => continue,
+        };
+        placement.entry(slot).or_default().push(item.clone());
+    }
+
+    // Order inside each page and folder: the side that changed it wins,
+    // items that arrived from the other side keep their index there
+    let mut merged = Layout {
+        folders,
+        ..local.clone()
+    };
+    merged.pages = Vec::new();
+
+    for (slot, members) in placement {
+        let (b, l, r) = (
+            contents(base, &slot),
+            contents(local, &slot),
+            contents(remote, &slot),
+        );
+        let (primary, secondary) = if l == b { (&r, &l) } else { (&l, &r) };
+
+        let member_set: HashSet<&ItemRef> = members.iter().collect();
+        let mut ordered: Vec<ItemRef> = primary
+            .iter()
+            .filter(|item| member_set.contains(item))
+            .cloned()
+            .collect();
+        let mut seen: HashSet<ItemRef> = ordered.iter().cloned().collect();
+        for item in &members {
+            if seen.insert(item.clone()) {
+                let index = secondary
+                    .iter()
+                    .position(|i| i == item)
+                    .unwrap_or(usize::MAX)
+                    .min(ordered.len());
+                ordered.insert(index, item.clone());
+            }
+        }
+
+        match slot {
+            Slot::Page(index) => {
+                while merged.pages.len() <= index {
+                    merged.pages.push(Page::default());
+                }
+                merged.pages[index].items = ordered;
+            }
+            Slot::Folder(id) => {
+                if let Some(folder) = merged.folders.iter_mut().find(|f| f.id == id) {
+                    folder.apps = ordered
+                        .into_iter()
+                        .filter_map(|item| match item {
+                            ItemRef::App { bundle_id } => Some(bundle_id),
+                            ItemRef::Folder { .. } => None,
+                        })
+                        .collect();
+                }
+            }
+        }
+    }
+
+    // Folders that ended up empty disappear (unless smart), folders without a page go last
+    merged
+        .folders
+        .retain(|f| !f.apps.is_empty() || f.rules.is_some());
+    let kept: HashSet<String> = merged.folders.iter().map(|f| f.id.clone()).collect();
+    for page in &mut merged.pages {
+        page.items.retain(|item| match item {
+            ItemRef::Folder { id } => kept.contains(id),
+            ItemRef::App { .. } => true,
+        });
+    }
+    let unplaced: Vec<String> = merged
+        .folders
+        .iter()
+        .filter(|f| merged.locate(&ItemRef::folder(&f.id)).is_none())
+        .map(|f| f.id.clone())
+        .collect();
+    for id in unplaced {
+        merged.append(ItemRef::folder(&id));
+    }
+
+    merged.hidden = merge_set(&base.hidden, &local.hidden, &remote.hidden);
+    let (favorites, conflict) = pick(&base.favorites, &local.favorites, &remote.favorites);
+    if conflict {
+        conflicts.push(MergeConflict {
+            kind: ConflictKind::Favorites,
+            subject: String::new(),
+        });
+    }
+    let placed: HashSet<String> = merged.all_bundle_ids().into_iter().collect();
+    merged.favorites = favorites
+        .into_iter()
+        .filter(|id| placed.contains(id))
+        .collect();
+    merged.custom_icons = merge_map(
+        &base.custom_icons,
+        &local.custom_icons,
+        &remote.custom_icons,
+    );
+    merged.reflow();
+
+    (merged, conflicts)
+}
+
+/// Merge the layout of a device we have never merged with. Without a common base nothing
+/// counts as a change, so differences aren't conflicts: local placement and names win, and
+/// the remote side only adds what local lacks. Apps that are loose here but in a folder
+/// there join that folder, so the remote side's folders aren't lost.
+pub fn merge_first_sync(local: &Layout, remote: &Layout) -> Layout {
+    let (mut merged, _) = merge_layouts(&Layout::default(), local, remote);
+
+    for folder in &remote.folders {
+        if merged.folder(&folder.id).is_some() {
+            continue;
+        }
+        let loose: Vec<String> = folder
+            .apps
+            .iter()
+            .filter(|id| merged.locate(&ItemRef::app(id)).is_some())
+            .cloned()
+            .collect();
+        if loose.is_empty() {
+            continue;
+        }
+        if let Err(e) = add_folder(&mut merged, folder, &loose) {
+            eprintln!("[LayoutSync] Failed to add folder {}: {}", folder.name, e);
+        }
+    }
+
+    merged
+}
+
+/// Add a folder (keeping its ID) holding loose apps, placed where the first of them was
+fn add_folder(layout: &mut Layout, folder: &Folder, bundle_ids: &[String]) -> Result<(), String> {
+    let (page, index) = layout
+        .locate(&ItemRef::app(&bundle_ids[0]))
+        .ok_or("App is not on a page")?;
+    layout.folders.push(Folder {
+        apps: Vec::new(),
+        ..folder.clone()
+    });
+    for bundle_id in bundle_ids {
+        layout.move_to_folder(bundle_id, &folder.id, None)?;
+    }
+    layout.move_item(ItemRef::folder(&folder.id), page, index)
+}
+
+fn merge_set(
+    base: &BTreeSet<String>,
+    local: &BTreeSet<String>,
+    remote: &BTreeSet<String>,
+) -> BTreeSet<String> {
+    local
+        .union(remote)
+        .chain(base)
+        .filter(|key| {
+            pick(
+                &base.contains(*key),
+                &local.contains(*key),
+                &remote.contains(*key),
+            )
+            .0
+        })
+        .cloned()
+        .collect()
+}
+
+fn merge_map(
+    base: &BTreeMap<String, String>,
+    local: &BTreeMap<String, String>,
+    remote: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    let keys: BTreeSet<&String> = base
+        .keys()
+        .chain(local.keys())
+        .chain(remote.keys())
+        .collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (value, _) = pick(&base.get(key), &local.get(key), &remote.get(key));
+            value.map(|v| (key.clone(), v.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Layout from pages of item names; names starting with "folder-" are folders
+    fn layout(pages: &[&[&str]], folders: &[(&str, &str, &[&str])]) -> Layout {
+        let item = |name: &&str| {
+            if name.starts_with("folder-") {
+                ItemRef::folder(name)
+            } else {
+                ItemRef::app(name)
+            }
+        };
+        Layout {
+            pages: pages
+                .iter()
+                .map(|items| Page {
+                    items: items.iter().map(item).collect(),
+                })
+                .collect(),
+            folders: folders
+                .iter()
+                .map(|(id, name, apps)| Folder {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    apps: apps.iter().map(|a| a.to_string()).collect(),
+                    rules: None,
+                })
+                .collect(),
+            ..Layout::default()
+        }
+    }
+
+    fn kinds(conflicts: &[MergeConflict]) -> Vec<ConflictKind> {
+        conflicts.iter().map(|c| c.kind).collect()
+    }
+
+    #[test]
+    fn concurrent_move_keeps_local_placement() {
+        let base = layout(&[&["a", "b"], &["c"], &["d"]], &[]);
+        let local = layout(&[&["b"], &["c", "a"], &["d"]], &[]);
+        let remote = layout(&[&["b"], &["c"], &["d", "a"]], &[]);
+
+        let (merged, conflicts) = merge_layouts(&base, &local, &remote);
+
+        assert_eq!(merged, local);
+        assert_eq!(kinds(&conflicts), [ConflictKind::Placement]);
+        assert_eq!(conflicts[0].subject, "a");
+    }
+
+    #[test]
+    fn one_sided_move_is_taken() {
+        let base = layout(&[&["a", "b"], &["c"]], &[]);
+        let remote = layout(&[&["b"], &["c", "a"]], &[]);
+
+        let (merged, conflicts) = merge_layouts(&base, &base, &remote);
+
+        assert_eq!(merged, remote);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn folder_renamed_on_both_sides_keeps_local_name() {
+        let base = layout(&[&["folder-1"]], &[("folder-1", "Dev", &["a"])]);
+        let local = layout(&[&["folder-1"]], &[("folder-1", "Code", &["a"])]);
+        let remote = layout(&[&["folder-1"]], &[("folder-1", "Tools", &["a"])]);
+
+        let (merged, conflicts) = merge_layouts(&base, &local, &remote);
+
+        assert_eq!(merged.folder("folder-1").unwrap().name, "Code");
+        assert_eq!(kinds(&conflicts), [ConflictKind::FolderName]);
+    }
+
+    #[test]
+    fn folder_edited_locally_survives_remote_delete() {
+        let base = layout(&[&["folder-1", "c"]], &[("folder-1", "Dev", &["a", "b"])]);
+        let local = layout(&[&["folder-1", "c"]], &[("folder-1", "Work", &["a", "b"])]);
+        let remote = layout(&[&["a", "b", "c"]], &[]);
+
+        let (merged, conflicts) = merge_layouts(&base, &local, &remote);
+
+        assert_eq!(merged, local);
+        assert_eq!(kinds(&conflicts), [ConflictKind::FolderDeleted]);
+    }
+
+    #[test]
+    fn folder_deleted_locally_stays_deleted() {
+        let base = layout(&[&["folder-1", "c"]], &[("folder-1", "Dev", &["a", "b"])]);
+        let local = layout(&[&["a", "b", "c"]], &[]);
+        let remote = layout(&[&["folder-1", "c"]], &[("folder-1", "Work", &["a", "b"])]);
+
+        let (merged, conflicts) = merge_layouts(&base, &local, &remote);
+
+        assert_eq!(merged, local);
+        assert_eq!(kinds(&conflicts), [ConflictKind::FolderDeleted]);
+    }
+
+    #[test]
+    fn first_sync_keeps_local_layout_and_adds_remote_folders() {
+        let local = layout(
+            &[&["folder-1", "c", "d", "e"], &["f"]],
+            &[("folder-1", "Mine", &["a", "b"])],
+        );
+        let remote = layout(
+            &[&["folder-2", "folder-3", "e"], &["a", "f"]],
+            &[
+                ("folder-2", "Theirs", &["c", "d"]),
+                ("folder-3", "Gone", &["x"]),
+            ],
+        );
+
+        let merged = merge_first_sync(&local, &remote);
+
+        assert_eq!(
+            merged,
+            layout(
+                &[&["folder-1", "folder-2", "e"], &["f"]],
+                &[
+                    ("folder-1", "Mine", &["a", "b"]),
+                    ("folder-2", "Theirs", &["c", "d"]),
+                ],
+            )
+        );
+    }
+}
@@ -0,0 +1,386 @@
+use crate::custom_icons;
+use crate::layout::{Layout, LayoutDiff};
+use crate::layout_merge::{self, MergeConflict};
+use crate::storage;
+use notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SYNC_STATE_FILE: &str = "layout-sync.json";
+
+/// Everything we write goes under <sync dir>/Launchpad/<profile id>/, so the folder can be shared
+const SYNC_SUBDIR: &str = "Launchpad";
+
+/// Custom icon files of all devices, next to the device layouts
+const ICONS_SUBDIR: &str = "icons";
+
+/// Wait for the sync client to finish writing before merging
+const SYNC_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// What a device publishes to the sync folder (<device id>.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceLayout {
+    pub device_id: String,
+    pub device_name: String,
+    /// Increases with every publish
+    pub seq: u64,
+    /// Seconds since the Unix epoch
+    pub updated_at: u64,
+    pub layout: Layout,
+}
+
+/// One line of a device's change log (<device id>.log.jsonl)
+#[derive(Debug, Serialize)]
+struct ChangeLogEntry<'a> {
+    seq: u64,
+    updated_at: u64,
+    device_name: &'a str,
+    changes: LayoutDiff,
+}
+
+/// The last version merged from another device; it is the common ancestor for the next merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RemoteState {
+    seq: u64,
+    layout: Layout,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ProfileSyncState {
+    #[serde(default)]
+    seq: u64,
+    /// Last layout we published, so unchanged layouts aren't written again
+    #[serde(default)]
+    published: Option<Layout>,
+    #[serde(default)]
+    remotes: BTreeMap<String, RemoteState>,
+}
+
+/// A conflict found while merging another device's layout
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncConflict {
+    pub device_name: String,
+    #[serde(flatten)]
+    pub conflict: MergeConflict,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Names of the devices whose changes were merged
+    pub merged: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub enabled: bool,
+    pub directory: Option<PathBuf>,
+    pub device_id: String,
+    pub device_name: String,
+}
+
+/// Opt-in layout sync through a shared folder (Dropbox, iCloud Drive, any local path).
+/// Each device publishes its own file, so sync clients never have to merge anything themselves.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LayoutSync {
+    #[serde(default)]
+    directory: Option<PathBuf>,
+    device_id: String,
+    device_name: String,
+    #[serde(default)]
+    profiles: BTreeMap<String, ProfileSyncState>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The Mac's name as shown in Sharing settings
+fn computer_name() -> String {
+    Command::new("scutil")
+        .args(["--get", "ComputerName"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "Mac".to_string())
+}
+
+/// Random enough ID for telling devices apart in the sync folder
+fn new_device_id(name: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    hasher.update(nanos.to_le_bytes());
+    hasher.update(std::process::id().to_le_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+impl LayoutSync {
+    /// Load the sync state, creating a device ID on first use
+    pub fn load() -> Self {
+        if let Some(sync) = storage::load_json(SYNC_STATE_FILE) {
+            return sync;
+        }
+
+        let device_name = computer_name();
+        let sync = Self {
+            directory: None,
+            device_id: new_device_id(&device_name),
+            device_name,
+            profiles: BTreeMap::new(),
+        };
+        if let Err(e) = sync.save() {
+            eprintln!("[LayoutSync] {}", e);
+        }
+        sync
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(SYNC_STATE_FILE, self)
+    }
+
+    pub fn status(&self) -> SyncStatus {
+        SyncStatus {
+            enabled: self.directory.is_some(),
+            directory: self.directory.clone(),
+            device_id: self.device_id.clone(),
+            device_name: self.device_name.clone(),
+        }
+    }
+
+    pub fn device_id(&self) -> &str {
+        &self.device_id
+    }
+
+    /// Folder watched for other devices' layouts, if sync is on
+    pub fn watch_dir(&self) -> Option<PathBuf> {
+        self.directory.as_ref().map(|d| d.join(SYNC_SUBDIR))
+    }
+
+    /// Turn sync on for a directory, or off with None. A new directory starts from scratch.
+    pub fn set_directory(&mut self, directory: Option<PathBuf>) -> Result<(), String> {
+        if let Some(ref dir) = directory {
+            if !dir.is_dir() {
+                return Err(format!("Not a directory: {}", dir.display()));
+            }
+            let sync_dir = dir.join(SYNC_SUBDIR);
+            fs::create_dir_all(&sync_dir)
+                .map_err(|e| format!("Failed to create {}: {}", sync_dir.display(), e))?;
+        }
+
+        if directory != self.directory {
+            self.profiles.clear();
+        }
+        self.directory = directory;
+        Ok(())
+    }
+
+    pub fn set_device_name(&mut self, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Device name cannot be empty".to_string());
+        }
+        self.device_name = name.to_string();
+        // Publish again under the new name
+        for state in self.profiles.values_mut() {
+            state.published = None;
+        }
+        Ok(())
+    }
+
+    fn profile_dir(&self, profile_id: &str) -> Result<Option<PathBuf>, String> {
+        let Some(dir) = self.watch_dir() else {
+            return Ok(None);
+        };
+        let dir = dir.join(profile_id);
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        Ok(Some(dir))
+    }
+
+    /// Write our layout to the sync folder and log what changed. Returns false if sync is
+    /// off or the layout is unchanged since the last publish.
+    pub fn publish(&mut self, profile_id: &str, layout: &Layout) -> Result<bool, String> {
+        let Some(dir) = self.profile_dir(profile_id)? else {
+            return Ok(false);
+        };
+
+        let state = self.profiles.entry(profile_id.to_string()).or_default();
+        if state.published.as_ref() == Some(layout) {
+            return Ok(false);
+        }
+
+        let changes = state.published.clone().unwrap_or_default().diff(layout);
+        state.seq += 1;
+        let device_layout = DeviceLayout {
+            device_id: self.device_id.clone(),
+            device_name: self.device_name.clone(),
+            seq: state.seq,
+            updated_at: now_secs(),
+            layout: layout.clone(),
+        };
+        // Custom icons are files; other devices copy them from here
+        custom_icons::copy_icons(&layout.custom_icons, &dir.join(ICONS_SUBDIR), true)?;
+        storage::write_json_atomic(
+            &dir.join(format!("{}.json", self.device_id)),
+            &device_layout,
+        )?;
+
+        let entry = ChangeLogEntry {
+            seq: device_layout.seq,
+            updated_at: device_layout.updated_at,
+            device_name: &self.device_name,
+            changes,
+        };
+        let log_path = dir.join(format!("{}.log.jsonl", self.device_id));
+        let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(|e| format!("Failed to write {}: {}", log_path.display(), e))?;
+
+        state.published = Some(layout.clone());
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Three-way merge every layout other devices published since we last looked into `local`.
+    /// The first layout seen from a device has no base: it only adds what we lack.
+    pub fn pull(&mut self, profile_id: &str, local: &mut Layout) -> Result<SyncReport, String> {
+        let dir = self
+            .profile_dir(profile_id)?
+            .ok_or("Layout sync is not enabled")?;
+        let entries =
+            fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+        let mut remotes: Vec<DeviceLayout> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| is_device_file(path, &self.device_id))
+            .filter_map(|path| {
+                let contents = fs::read_to_string(&path).ok()?;
+                match serde_json::from_str(&contents) {
+                    Ok(device_layout) => Some(device_layout),
+                    Err(e) => {
+                        eprintln!("[LayoutSync] Skipping {}: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        remotes.sort_by_key(|d| d.updated_at);
+
+        let state = self.profiles.entry(profile_id.to_string()).or_default();
+        let mut report = SyncReport::default();
+
+        for remote in remotes {
+            let known = state.remotes.get(&remote.device_id);
+            if known.is_some_and(|k| k.seq >= remote.seq) {
+                continue;
+            }
+
+            if let Err(e) = custom_icons::copy_icons(
+                &remote.layout.custom_icons,
+                &dir.join(ICONS_SUBDIR),
+                false,
+            ) {
+                eprintln!("[LayoutSync] {}", e);
+            }
+
+            let (merged, conflicts) = match known {
+                Some(known) => layout_merge::merge_layouts(&known.layout, local, &remote.layout),
+                None => (
+                    layout_merge::merge_first_sync(local, &remote.layout),
+                    Vec::new(),
+                ),
+            };
+            *local = merged;
+
+            report
+                .conflicts
+                .extend(conflicts.into_iter().map(|conflict| SyncConflict {
+                    device_name: remote.device_name.clone(),
+                    conflict,
+                }));
+            report.merged.push(remote.device_name.clone());
+            state.remotes.insert(
+                remote.device_id.clone(),
+                RemoteState {
+                    seq: remote.seq,
+                    layout: remote.layout,
+                },
+            );
+        }
+
+        self.save()?;
+        Ok(report)
+    }
+}
+
+/// Layout files of other devices (skips our own, change logs and temp files)
+fn is_device_file(path: &Path, own_device_id: &str) -> bool {
+    let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    file_name.ends_with(".json")
+        && !file_name.starts_with('.')
+        && file_name != format!("{}.json", own_device_id)
+}
+
+/// Watches the sync folder and calls back when another device publishes a layout
+pub struct SyncWatcher {
+    _debouncer: Debouncer<notify::RecommendedWatcher>,
+}
+
+impl SyncWatcher {
+    pub fn start<F>(watch_dir: &Path, own_device_id: &str, callback: F) -> Result<Self, String>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let own_device_id = own_device_id.to_string();
+        let mut debouncer =
+            new_debouncer(
+                SYNC_DEBOUNCE,
+                move |result: DebounceEventResult| match result {
+                    Ok(events) => {
+                        if events
+                            .iter()
+                            .any(|e| is_device_file(&e.path, &own_device_id))
+                        {
+                            callback();
+                        }
+                    }
+                    Err(error) => eprintln!("[LayoutSync] Watch error: {:?}", error),
+                },
+            )
+            .map_err(|e| format!("Failed to watch sync folder: {}", e))?;
+
+        debouncer
+            .watcher()
+            .watch(watch_dir, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {}: {}", watch_dir.display(), e))?;
+
+        Ok(Self {
+            _debouncer: debouncer,
+        })
+    }
+}
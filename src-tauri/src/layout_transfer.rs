@@ -0,0 +1,364 @@
+use crate::custom_icons;
+use crate::layout::{Folder, ItemRef, Layout, Page};
+use crate::smart_folders::SmartRules;
+use crate::storage;
+use crate::tag_store::CustomTag;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Marks files written by export_layout
+pub const EXPORT_FORMAT: &str = "launchpad-layout";
+
+/// Current layout file format version
+pub const EXPORT_VERSION: u32 = 1;
+
+/// A portable layout file. Apps are referenced by bundle ID only, so the file applies on any machine:
+///
+/// ```json
+/// {
+///   "format": "launchpad-layout",
+///   "version": 1,
+///   "page_size": 70,
+///   "pages": [
+///     [
+///       { "type": "app", "bundle_id": "com.apple.Safari" },
+///       { "type": "folder", "name": "Dev", "apps": ["com.microsoft.VSCode", "com.apple.dt.Xcode"] },
+///       { "type": "folder", "name": "Recent", "apps": [], "rules": { "mode": "all", "rules": [{ "type": "installed-within", "days": 14 }] } }
+///     ]
+///   ],
+///   "hidden": ["com.apple.Chess"],
+///   "favorites": ["com.apple.Safari", "com.apple.mail"],
+///   "custom_icons": { "com.apple.Safari": "data:image/png;base64,..." },
+///   "custom_tags": [{ "key": "custom-work", "label": "Work", "icon_name": "BriefcaseIcon" }],
+///   "tags": { "com.apple.Safari": ["browsers", "custom-work"] },
+///   "app_names": { "com.apple.Safari": "Safari" }
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutExport {
+    pub format: String,
+    pub version: u32,
+    pub page_size: usize,
+    /// Pages in order, each a list of apps and folders in grid order
+    pub pages: Vec<Vec<ExportItem>>,
+    #[serde(default)]
+    pub hidden: BTreeSet<String>,
+    /// Pinned apps in order
+    #[serde(default)]
+    pub favorites: Vec<String>,
+    /// PNG data URLs by bundle ID
+    #[serde(default)]
+    pub custom_icons: BTreeMap<String, String>,
+    #[serde(default)]
+    pub custom_tags: Vec<CustomTag>,
+    /// Effective tags by bundle ID
+    #[serde(default)]
+    pub tags: BTreeMap<String, Vec<String>>,
+    /// App names at export time, used to report missing apps by name
+    #[serde(default)]
+    pub app_names: BTreeMap<String, String>,
+}
+
+/// An item on an exported page. Folders are inlined since folder IDs are local.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ExportItem {
+    App {
+        bundle_id: String,
+    },
+    Folder {
+        name: String,
+        apps: Vec<String>,
+        /// Rules of a smart folder
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rules: Option<SmartRules>,
+    },
+}
+
+/// How an imported layout is combined with the current one
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportStrategy {
+    /// Use the imported pages, folders, tags, icons, hidden apps and favorites; local-only apps go at the end
+    Replace,
+    /// Use the imported arrangement but keep local folders, tags, icons, hidden apps and favorites it doesn't cover
+    Merge,
+    /// Only create (or fill) the imported folders, leaving everything else as it is
+    FoldersOnly,
+}
+
+/// An app referenced by the imported file that isn't installed here
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingApp {
+    pub bundle_id: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutImportReport {
+    pub strategy: ImportStrategy,
+    /// Number of referenced apps that are installed here
+    pub placed: usize,
+    pub missing: Vec<MissingApp>,
+}
+
+/// Turn the stored layout (plus tag data and app names) into a portable file
+pub fn export_layout(
+    layout: &Layout,
+    custom_tags: &[CustomTag],
+    tags: BTreeMap<String, Vec<String>>,
+    app_names: BTreeMap<String, String>,
+) -> LayoutExport {
+    let pages = layout
+        .pages
+        .iter()
+        .map(|page| {
+            page.items
+                .iter()
+                .filter_map(|item| match item {
+                    ItemRef::App { bundle_id } => Some(ExportItem::App {
+                        bundle_id: bundle_id.clone(),
+                    }),
+                    ItemRef::Folder { id } => layout.folder(id).map(|f| ExportItem::Folder {
+                        name: f.name.clone(),
+                        apps: f.apps.clone(),
+                        rules: f.rules.clone(),
+                    }),
+                })
+                .collect()
+        })
+        .collect();
+
+    LayoutExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        page_size: layout.page_size,
+        pages,
+        hidden: layout.hidden.clone(),
+        favorites: layout.favorites.clone(),
+        custom_icons: custom_icons::resolve_all(&layout.custom_icons),
+        custom_tags: custom_tags.to_vec(),
+        tags,
+        app_names,
+    }
+}
+
+pub fn write_export(path: &Path, export: &LayoutExport) -> Result<(), String> {
+    storage::write_json_atomic(path, export)
+}
+
+/// Read and check a layout file written by export_layout
+pub fn read_export(path: &Path) -> Result<LayoutExport, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let export: LayoutExport = serde_json::from_str(&contents)
+        .map_err(|e| format!("Not a layout file: {}", e))?;
+
+    if export.format != EXPORT_FORMAT {
+        return Err(format!("Not a layout file (format \"{}\")", export.format));
+    }
+    if export.version > EXPORT_VERSION {
+        return Err(format!(
+            "Layout file version {} is newer than supported version {}",
+            export.version, EXPORT_VERSION
+        ));
+    }
+
+    Ok(export)
+}
+
+/// Apply an imported layout to `layout` according to the strategy.
+/// Tags are applied separately by the caller since they live in the tag store.
+pub fn apply_import(
+    layout: &mut Layout,
+    export: &LayoutExport,
+    installed: &[String],
+    strategy: ImportStrategy,
+) -> LayoutImportReport {
+    let installed_set: HashSet<&String> = installed.iter().collect();
+
+    let mut referenced = Vec::new();
+    for item in export.pages.iter().flatten() {
+        match item {
+            ExportItem::App { bundle_id } => referenced.push(bundle_id),
+            ExportItem::Folder { apps, .. } => referenced.extend(apps),
+        }
+    }
+    let mut seen = HashSet::new();
+    referenced.retain(|id| seen.insert(*id));
+
+    let (present, absent): (Vec<&String>, Vec<&String>) = referenced
+        .into_iter()
+        .partition(|id| installed_set.contains(id));
+
+    match strategy {
+        ImportStrategy::Replace => {
+            let mut imported = build_layout(export, &installed_set);
+            imported.hidden = export.hidden.clone();
+            imported.favorites = export.favorites.clone();
+            imported.custom_icons = export.custom_icons.clone();
+            imported.legacy_imported = layout.legacy_imported;
+            imported.sync_apps(installed);
+            *layout = imported;
+        }
+        ImportStrategy::Merge => {
+            let mut imported = build_layout(export, &installed_set);
+            keep_local_extras(&mut imported, layout);
+            imported.hidden = layout.hidden.union(&export.hidden).cloned().collect();
+            imported.favorites = layout.favorites.clone();
+            for bundle_id in &export.favorites {
+                if !imported.favorites.contains(bundle_id) {
+                    imported.favorites.push(bundle_id.clone());
+                }
+            }
+            imported.custom_icons = layout.custom_icons.clone();
+            imported
+                .custom_icons
+                .extend(export.custom_icons.iter().map(|(k, v)| (k.clone(), v.clone())));
+            imported.legacy_imported = layout.legacy_imported;
+            imported.sync_apps(installed);
+            *layout = imported;
+        }
+        ImportStrategy::FoldersOnly => {
+            for item in export.pages.iter().flatten() {
+                if let ExportItem::Folder { name, apps, .. } = item {
+                    import_folder(layout, name, apps, &installed_set);
+                }
+            }
+        }
+    }
+
+    LayoutImportReport {
+        strategy,
+        placed: present.len(),
+        missing: absent
+            .into_iter()
+            .map(|id| MissingApp {
+                bundle_id: id.clone(),
+                name: export.app_names.get(id).cloned(),
+            })
+            .collect(),
+    }
+}
+
+/// Pages and folders of the imported file, without apps that aren't installed
+fn build_layout(export: &LayoutExport, installed: &HashSet<&String>) -> Layout {
+    let mut layout = Layout {
+        page_size: export.page_size.max(1),
+        ..Layout::default()
+    };
+    let mut placed: HashSet<&String> = HashSet::new();
+
+    for exported_page in &export.pages {
+        let mut page = Page::default();
+
+        for item in exported_page {
+            match item {
+                ExportItem::App { bundle_id } => {
+                    if installed.contains(bundle_id) && placed.insert(bundle_id) {
+                        page.items.push(ItemRef::app(bundle_id));
+                    }
+                }
+                ExportItem::Folder { name, apps, rules } => {
+                    let apps: Vec<String> = apps
+                        .iter()
+                        .filter(|id| installed.contains(id) && placed.insert(id))
+                        .cloned()
+                        .collect();
+                    if apps.is_empty() && rules.is_none() {
+                        continue;
+                    }
+
+                    let folder = Folder {
+                        id: layout.new_folder_id(),
+                        name: name.clone(),
+                        apps,
+                        rules: rules.clone(),
+                    };
+                    page.items.push(ItemRef::folder(&folder.id));
+                    layout.folders.push(folder);
+                }
+            }
+        }
+
+        if !page.items.is_empty() {
+            layout.pages.push(page);
+        }
+    }
+
+    layout.reflow();
+    layout
+}
+
+/// Append local folders and apps the imported layout doesn't place, in their local order
+fn keep_local_extras(imported: &mut Layout, local: &Layout) {
+    let mut placed: HashSet<String> = imported.all_bundle_ids().into_iter().collect();
+
+    for item in local.pages.iter().flat_map(|p| &p.items) {
+        match item {
+            ItemRef::App { bundle_id } => {
+                if placed.insert(bundle_id.clone()) {
+                    imported.append(ItemRef::app(bundle_id));
+                }
+            }
+            ItemRef::Folder { id } => {
+                let Some(folder) = local.folder(id) else {
+                    continue;
+                };
+                let apps: Vec<String> = folder
+                    .apps
+                    .iter()
+                    .filter(|a| placed.insert((*a).clone()))
+                    .cloned()
+                    .collect();
+                if apps.is_empty() && folder.rules.is_none() {
+                    continue;
+                }
+
+                let id = if imported.folder(&folder.id).is_some() {
+                    imported.new_folder_id()
+                } else {
+                    folder.id.clone()
+                };
+                imported.folders.push(Folder {
+                    id: id.clone(),
+                    name: folder.name.clone(),
+                    apps,
+                    rules: folder.rules.clone(),
+                });
+                imported.append(ItemRef::folder(&id));
+            }
+        }
+    }
+}
+
+/// Fill the local folder with the same name (case-insensitive), or create it where its first app is
+fn import_folder(layout: &mut Layout, name: &str, apps: &[String], installed: &HashSet<&String>) {
+    let apps: Vec<String> = apps
+        .iter()
+        .filter(|id| installed.contains(id))
+        .cloned()
+        .collect();
+    if apps.is_empty() {
+        return;
+    }
+
+    let existing = layout
+        .folders
+        .iter()
+        .find(|f| f.name.to_lowercase() == name.to_lowercase())
+        .map(|f| f.id.clone());
+
+    let result = match existing {
+        Some(folder_id) => apps
+            .iter()
+            .try_for_each(|id| layout.move_to_folder(id, &folder_id, None)),
+        None => layout.create_folder(name, &apps).map(|_| ()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("[Layout] Failed to import folder {}: {}", name, e);
+    }
+}
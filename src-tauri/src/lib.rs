@@ -3,6 +3,7 @@ use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use tauri::{Emitter, Manager};
@@ -10,13 +11,141 @@ use walkdir::WalkDir;
 
 mod hot_corners;
 mod app_watcher;
+mod app_inventory;
+mod storage;
+mod tag_store;
+mod finder_tags;
+mod web_apps;
+mod itunes_metadata;
+mod layout;
+mod launchpad_import;
+mod dock_seed;
+mod layout_transfer;
+mod layout_history;
+mod profiles;
+mod layout_merge;
+mod layout_sync;
+mod settings;
+mod placement;
+mod usage;
+mod app_arch;
+mod smart_folders;
+mod auto_organize;
+mod collation;
+mod hidden_apps;
+mod display_names;
+mod install_history;
+mod launch_options;
+mod launch_error;
+mod sorting;
+mod custom_icons;
 
 use hot_corners::{Corner, HotCornerConfig, HotCornerMonitor};
 use app_watcher::AppWatcher;
+use app_inventory::{AppChanges, AppInventory, InstalledApp};
+use tag_store::{CustomTag, TagStore};
+use web_apps::{AppKind, WebAppInfo};
+use layout::{Folder, ItemRef, Layout, LayoutDiff, LegacyFolder};
+use layout_history::{SnapshotInfo, SnapshotReason, UndoHistory, UndoState};
+use layout_transfer::{ImportStrategy, LayoutImportReport};
+use profiles::{Profile, ProfileStore};
+use layout_sync::{LayoutSync, SyncReport, SyncStatus, SyncWatcher};
+use settings::Settings;
+use collation::NameCollator;
+use hidden_apps::{HiddenApp, HiddenApps, HiddenRules};
+use display_names::DisplayNames;
+use install_history::{InstallHistory, RecentApp};
+use launch_options::{LaunchOptions, LaunchPreset, LaunchPresets};
+use launch_error::{LaunchError, LaunchErrorKind};
+use smart_folders::{AppFacts, SmartRules};
+use auto_organize::{OrganizeApp, OrganizeOptions, OrganizePreview};
+use sorting::{SortFacts, SortKey, SortScope};
+use usage::UsageStore;
 use std::sync::Mutex;
 
 static HOT_CORNER_MONITOR: OnceLock<HotCornerMonitor> = OnceLock::new();
 static APP_WATCHER: OnceLock<Mutex<AppWatcher>> = OnceLock::new();
+static APP_INVENTORY: Mutex<Option<AppInventory>> = Mutex::new(None);
+static TAG_STORE: OnceLock<Mutex<TagStore>> = OnceLock::new();
+static LAYOUT_STORE: OnceLock<Mutex<Layout>> = OnceLock::new();
+static USAGE_STORE: OnceLock<Mutex<UsageStore>> = OnceLock::new();
+static HIDDEN_RULES: OnceLock<Mutex<HiddenRules>> = OnceLock::new();
+static DISPLAY_NAMES: OnceLock<Mutex<DisplayNames>> = OnceLock::new();
+static INSTALL_HISTORY: OnceLock<Mutex<InstallHistory>> = OnceLock::new();
+static LAUNCH_PRESETS: OnceLock<Mutex<LaunchPresets>> = OnceLock::new();
+static UNDO_HISTORY: OnceLock<Mutex<UndoHistory>> = OnceLock::new();
+static PROFILE_STORE: OnceLock<Mutex<ProfileStore>> = OnceLock::new();
+static SETTINGS: OnceLock<Mutex<Settings>> = OnceLock::new();
+static LAYOUT_SYNC: OnceLock<Mutex<LayoutSync>> = OnceLock::new();
+static SYNC_WATCHER: Mutex<Option<SyncWatcher>> = Mutex::new(None);
+static PROFILE_SHORTCUTS: OnceLock<Mutex<Vec<tauri_plugin_global_shortcut::Shortcut>>> =
+    OnceLock::new();
+
+/// Get the tag store, loading it from disk on first use
+fn tag_store() -> &'static Mutex<TagStore> {
+    TAG_STORE.get_or_init(|| Mutex::new(TagStore::load()))
+}
+
+/// Get the layout store, loading the active profile's layout from disk on first use
+fn layout_store() -> &'static Mutex<Layout> {
+    LAYOUT_STORE.get_or_init(|| Mutex::new(Layout::load(&active_profile_id())))
+}
+
+/// Get the profile store, loading it from disk on first use
+fn profile_store() -> &'static Mutex<ProfileStore> {
+    PROFILE_STORE.get_or_init(|| Mutex::new(ProfileStore::load()))
+}
+
+/// Get the settings, loading (and migrating) them from disk on first use
+fn settings() -> &'static Mutex<Settings> {
+    SETTINGS.get_or_init(|| Mutex::new(Settings::load()))
+}
+
+/// Collator for the UI language, used wherever the backend sorts app names
+fn name_collator() -> NameCollator {
+    let language = settings().lock().unwrap().language.clone();
+    NameCollator::for_language(&language)
+}
+
+/// Get the hidden path rules, loading them from disk on first use
+fn hidden_rules() -> &'static Mutex<HiddenRules> {
+    HIDDEN_RULES.get_or_init(|| Mutex::new(HiddenRules::load()))
+}
+
+/// Get the display name overrides, loading them from disk on first use
+fn display_names() -> &'static Mutex<DisplayNames> {
+    DISPLAY_NAMES.get_or_init(|| Mutex::new(DisplayNames::load()))
+}
+
+/// Get the install and update times, loading them from disk on first use
+fn install_history() -> &'static Mutex<InstallHistory> {
+    INSTALL_HISTORY.get_or_init(|| Mutex::new(InstallHistory::load()))
+}
+
+/// Get the saved launch presets, loading them from disk on first use
+fn launch_presets() -> &'static Mutex<LaunchPresets> {
+    LAUNCH_PRESETS.get_or_init(|| Mutex::new(LaunchPresets::load()))
+}
+
+/// Get the launch statistics, loading them from disk on first use
+fn usage_store() -> &'static Mutex<UsageStore> {
+    USAGE_STORE.get_or_init(|| Mutex::new(UsageStore::load()))
+}
+
+/// Get the layout sync state, loading it from disk on first use
+fn layout_sync() -> &'static Mutex<LayoutSync> {
+    LAYOUT_SYNC.get_or_init(|| Mutex::new(LayoutSync::load()))
+}
+
+/// ID of the active profile (lock order: layout store before profile store)
+fn active_profile_id() -> String {
+    profile_store().lock().unwrap().active_id().to_string()
+}
+
+/// Get the layout undo history, loading it from disk on first use
+fn undo_history() -> &'static Mutex<UndoHistory> {
+    UNDO_HISTORY.get_or_init(|| Mutex::new(UndoHistory::load()))
+}
 
 /// Get the icon cache directory, creating it if it doesn't exist
 fn get_icon_cache_dir() -> Option<PathBuf> {
@@ -72,7 +201,21 @@ struct App {
     path: String,
     icon: Option<String>,          // Base64 encoded icon
     source_folder: Option<String>, // Track where the app came from
-    tags: Vec<String>,             // Auto-detected category tags
+    tags: Vec<String>,             // Category tags (auto-detected + user overrides)
+    #[serde(default)]
+    kind: AppKind, // Regular app or browser-installed web app
+    #[serde(default)]
+    web_app: Option<WebAppInfo>, // Site URL and host browser for web apps
+    #[serde(default)]
+    developer: Option<String>, // App Store artist for iOS apps
+    #[serde(default)]
+    genre: Option<String>, // App Store genre for iOS apps
+    #[serde(default)]
+    original_name: Option<String>, // Bundle's own name when the user renamed the app
+    #[serde(default)]
+    is_new: bool, // Installed recently and not launched since
+    #[serde(default)]
+    recently_updated: bool, // Updated recently and not launched since
 }
 
 /// Event payload for icon updates
@@ -91,51 +234,92 @@ struct AppMetadata {
     actual_app_path: PathBuf, // Path to the actual app bundle (for icon extraction)
     source_folder: Option<String>,
     tags: Vec<String>,
+    web_app: Option<WebAppInfo>,
+    developer: Option<String>,
+    genre: Option<String>,
+    artwork_path: Option<PathBuf>, // App Store artwork for wrapped iOS apps
+    version: Option<String>,
+    original_name: Option<String>, // Bundle's own name when the user renamed the app
 }
 
-/// Get installed apps WITHOUT icons - this is the fast path for immediate display
-#[tauri::command]
-fn get_installed_apps_fast() -> Result<Vec<App>, String> {
-    let mut app_metadata = Vec::new();
+/// A directory apps are scanned from
+struct AppRoot {
+    path: PathBuf,
+    source_folder: Option<&'static str>,
+    max_depth: usize,
+}
 
-    // Scan all application directories (fast - no icon extraction)
-    scan_applications_directory_fast("/Applications", None, &mut app_metadata, 2);
-    scan_applications_directory_fast("/System/Applications", Some("System"), &mut app_metadata, 1);
-    scan_applications_directory_fast(
-        "/System/Applications/Utilities",
-        Some("Utilities"),
-        &mut app_metadata,
-        1,
-    );
-    scan_applications_directory_fast(
-        "/Applications/Utilities",
-        Some("Utilities"),
-        &mut app_metadata,
-        1,
-    );
+/// All app directories, in the order they are scanned (earlier roots win for duplicate bundle IDs)
+fn app_roots() -> Vec<AppRoot> {
+    let root = |path: PathBuf, source_folder, max_depth| AppRoot {
+        path,
+        source_folder,
+        max_depth,
+    };
 
-    // Scan user Applications folder
+    let mut roots = vec![
+        root(PathBuf::from("/Applications"), None, 2),
+        root(PathBuf::from("/System/Applications"), Some("System"), 1),
+        root(PathBuf::from("/System/Applications/Utilities"), Some("Utilities"), 1),
+        root(PathBuf::from("/Applications/Utilities"), Some("Utilities"), 1),
+    ];
+
+    // User Applications folder
     if let Some(home_dir) = std::env::var_os("HOME") {
-        let user_apps_path = PathBuf::from(home_dir).join("Applications");
-        if user_apps_path.exists() {
-            scan_applications_directory_fast(
-                user_apps_path.to_str().unwrap_or(""),
-                None,
-                &mut app_metadata,
-                2,
-            );
+        roots.push(root(PathBuf::from(home_dir).join("Applications"), None, 2));
+    }
+
+    roots
+}
+
+/// Scan one root without extracting icons, with the user's display names applied
+fn scan_app_root(root: &AppRoot) -> Vec<AppMetadata> {
+    let mut app_metadata = Vec::new();
+    if root.path.exists() {
+        scan_applications_directory_fast(
+            root.path.to_str().unwrap_or(""),
+            root.source_folder,
+            &mut app_metadata,
+            root.max_depth,
+        );
+    }
+
+    let names = display_names().lock().unwrap();
+    for metadata in &mut app_metadata {
+        if let Some(custom) = names.get(&metadata.bundle_id) {
+            metadata.original_name = Some(std::mem::replace(&mut metadata.name, custom.clone()));
         }
     }
 
+    app_metadata
+}
+
+/// Scan all application directories without extracting icons, deduplicated by bundle ID,
+/// with the user's display names applied
+fn scan_all_app_metadata() -> Vec<AppMetadata> {
+    // Scan all application directories (fast - no icon extraction)
+    let mut app_metadata: Vec<AppMetadata> = app_roots().iter().flat_map(scan_app_root).collect();
+
     // Remove duplicates based on bundle_id
     app_metadata.sort_by(|a, b| a.bundle_id.cmp(&b.bundle_id));
     app_metadata.dedup_by(|a, b| a.bundle_id == b.bundle_id);
 
-    // Sort alphabetically by name
-    app_metadata.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    app_metadata
+}
+
+/// Get installed apps WITHOUT icons - this is the fast path for immediate display
+#[tauri::command]
+fn get_installed_apps_fast() -> Result<Vec<App>, String> {
+    let mut app_metadata = scan_all_app_metadata();
+
+    record_scanned_apps(&app_metadata, true, &[]);
+
+    // Sort alphabetically by name, following the UI language
+    let collator = name_collator();
+    app_metadata.sort_by(|a, b| collator.compare(&a.name, &b.name));
 
     // Convert to App structs without icons
-    let apps: Vec<App> = app_metadata
+    let mut apps: Vec<App> = app_metadata
         .into_iter()
         .map(|m| App {
             name: m.name,
@@ -144,59 +328,412 @@ fn get_installed_apps_fast() -> Result<Vec<App>, String> {
             icon: None,
             source_folder: m.source_folder,
             tags: m.tags,
+            kind: if m.web_app.is_some() {
+                AppKind::WebApp
+            } else {
+                AppKind::App
+            },
+            web_app: m.web_app,
+            developer: m.developer,
+            genre: m.genre,
+            original_name: m.original_name,
+            is_new: false,
+            recently_updated: false,
         })
         .collect();
 
+    apply_stored_tags(&mut apps);
+    apply_badges(&mut apps);
+    // Hidden apps stay in the layout so they get their place back when shown again
+    sync_layout_with_apps(&apps);
+    remove_hidden_apps(&mut apps);
+
     Ok(apps)
 }
 
-/// Load app icons in parallel using rayon, emitting updates as they're ready
-#[tauri::command]
-async fn load_app_icons(app: tauri::AppHandle) -> Result<(), String> {
-    let mut app_metadata = Vec::new();
+/// Remember when each app was first seen and when its version changed. `removed` are apps
+/// a partial scan (of some roots) found gone; a full scan works that out itself.
+fn record_scanned_apps(metadata: &[AppMetadata], full_scan: bool, removed: &[String]) {
+    let mut history = install_history().lock().unwrap();
+    let scanned = metadata
+        .iter()
+        .map(|m| (m.bundle_id.as_str(), m.version.as_deref()));
+    let mut changed = history.observe(scanned, full_scan, usage::now_secs());
+    changed |= history.forget(removed);
+    if changed {
+        if let Err(e) = history.save() {
+            eprintln!("[InstallHistory] {}", e);
+        }
+    }
+}
 
-    // Scan all application directories (fast - no icon extraction)
-    scan_applications_directory_fast("/Applications", None, &mut app_metadata, 2);
-    scan_applications_directory_fast("/System/Applications", Some("System"), &mut app_metadata, 1);
-    scan_applications_directory_fast(
-        "/System/Applications/Utilities",
-        Some("Utilities"),
-        &mut app_metadata,
-        1,
-    );
-    scan_applications_directory_fast(
-        "/Applications/Utilities",
-        Some("Utilities"),
-        &mut app_metadata,
-        1,
+/// Set the "New" and "Updated" badges from the install history and launches
+fn apply_badges(apps: &mut [App]) {
+    let days = settings().lock().unwrap().new_badge_days;
+    let usage = usage_store().lock().unwrap();
+    let history = install_history().lock().unwrap();
+    let now = usage::now_secs();
+    for app in apps.iter_mut() {
+        let last_launched = usage.get(&app.bundle_id).and_then(|u| u.last_launched);
+        (app.is_new, app.recently_updated) =
+            history.badges(&app.bundle_id, last_launched, days, now);
+    }
+}
+
+/// Whether an app is hidden: by bundle ID (`hidden` is the active layout's set, so callers
+/// holding the layout can pass it) or by a path rule
+fn is_hidden(hidden: &BTreeSet<String>, bundle_id: &str, path: &str) -> bool {
+    hidden.contains(bundle_id) || hidden_rules().lock().unwrap().matching_rule(path).is_some()
+}
+
+/// Bundle ID -> path of the apps in the inventory, for the hidden path rules
+fn inventory_paths() -> HashMap<String, String> {
+    APP_INVENTORY
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|inventory| {
+            inventory
+                .apps()
+                .into_iter()
+                .map(|(bundle_id, app)| (bundle_id, app.path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Drop apps hidden by bundle ID or by a path rule
+fn remove_hidden_apps(apps: &mut Vec<App>) {
+    let hidden = layout_store().lock().unwrap().hidden.clone();
+    apps.retain(|app| !is_hidden(&hidden, &app.bundle_id, &app.path));
+}
+
+fn installed_app(metadata: &AppMetadata) -> InstalledApp {
+    InstalledApp {
+        bundle_id: metadata.bundle_id.clone(),
+        name: metadata.name.clone(),
+        path: metadata.path.clone(),
+        version: metadata.version.clone(),
+    }
+}
+
+/// Scan every root into the inventory the watcher diffs against
+fn init_app_inventory() {
+    let mut scanned = AppInventory::default();
+    for root in app_roots() {
+        let apps = scan_app_root(&root).iter().map(installed_app).collect();
+        scanned.set_root(&root.path, apps);
+    }
+    *APP_INVENTORY.lock().unwrap() = Some(scanned);
+}
+
+/// Rescan the roots the changed paths are in, update the layout and emit
+/// "apps-changed" with what was added, removed, moved or updated
+fn refresh_changed_apps(app: &tauri::AppHandle, paths: &[PathBuf]) {
+    let roots: Vec<AppRoot> = app_roots()
+        .into_iter()
+        .filter(|root| paths.iter().any(|p| p.starts_with(&root.path)))
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+
+    let (changes, scanned) = {
+        let mut inventory = APP_INVENTORY.lock().unwrap();
+        let Some(inventory) = inventory.as_mut() else {
+            return;
+        };
+        let before = inventory.apps();
+        let mut scanned = Vec::new();
+        for root in &roots {
+            let metadata = scan_app_root(root);
+            inventory.set_root(&root.path, metadata.iter().map(installed_app).collect());
+            scanned.extend(metadata);
+        }
+        let changes = app_inventory::diff(&before, &inventory.apps(), &name_collator());
+        (changes, scanned)
+    };
+    if changes.is_empty() {
+        return;
+    }
+
+    // New apps need their detected tags before they can be placed by tag
+    {
+        let mut store = tag_store().lock().unwrap();
+        let mut changed = false;
+        for metadata in &scanned {
+            changed |= store.set_detected(&metadata.bundle_id, &metadata.tags);
+        }
+        if changed {
+            if let Err(e) = store.save() {
+                eprintln!("[TagStore] {}", e);
+            }
+        }
+    }
+
+    let removed: Vec<String> = changes.removed.iter().map(|a| a.bundle_id.clone()).collect();
+    record_scanned_apps(&scanned, false, &removed);
+    apply_app_changes(app, &changes);
+    println!(
+        "[AppWatcher] {} added, {} removed, {} moved, {} updated",
+        changes.added.len(),
+        changes.removed.len(),
+        changes.moved.len(),
+        changes.updated.len()
     );
+    let _ = app.emit("apps-changed", changes);
+}
 
-    // Scan user Applications folder
-    if let Some(home_dir) = std::env::var_os("HOME") {
-        let user_apps_path = PathBuf::from(home_dir).join("Applications");
-        if user_apps_path.exists() {
-            scan_applications_directory_fast(
-                user_apps_path.to_str().unwrap_or(""),
-                None,
-                &mut app_metadata,
-                2,
-            );
+/// New apps (sorted by name) are placed by the placement policy, removed ones are dropped
+fn apply_app_changes(app: &tauri::AppHandle, changes: &AppChanges) {
+    let added: Vec<String> = changes.added.iter().map(|a| a.bundle_id.clone()).collect();
+    let removed: Vec<String> = changes.removed.iter().map(|a| a.bundle_id.clone()).collect();
+    let (policy, folder_name) = {
+        let settings = settings().lock().unwrap();
+        (settings.new_app_placement, settings.new_apps_folder_name.clone())
+    };
+
+    // Gathering facts scans every app, so it happens before the layout is locked
+    let (has_smart_folders, hidden) = {
+        let layout = layout_store().lock().unwrap();
+        let has_smart_folders = layout.folders.iter().any(|f| f.rules.is_some());
+        (has_smart_folders, layout.hidden.clone())
+    };
+    let facts = has_smart_folders.then(|| installed_app_facts(&hidden));
+
+    let mut layout = layout_store().lock().unwrap();
+    let mut changed = layout.apply_app_changes(&added, &removed);
+    if changed {
+        let store = tag_store().lock().unwrap();
+        let tags_for = |bundle_id: &str| store.tags_for(bundle_id);
+        if let Err(e) =
+            placement::place_new_apps(&mut layout, &added, policy, &folder_name, tags_for)
+        {
+            eprintln!("[Layout] Failed to place new apps: {}", e);
         }
     }
+    changed |= refresh_smart_folders(&mut layout, |hidden| {
+        facts.unwrap_or_else(|| installed_app_facts(hidden))
+    });
+    if !changed {
+        return;
+    }
 
-    // Remove duplicates
-    app_metadata.sort_by(|a, b| a.bundle_id.cmp(&b.bundle_id));
-    app_metadata.dedup_by(|a, b| a.bundle_id == b.bundle_id);
+    if let Err(e) = layout.save(&active_profile_id()) {
+        eprintln!("[Layout] {}", e);
+    }
+    publish_layout(&layout);
+    let _ = app.emit("layout-changed", layout.clone());
+}
+
+/// Scanned bundle IDs in the alphabetical order a fresh layout uses
+fn installed_bundle_ids() -> Vec<String> {
+    let mut apps = scan_all_app_metadata();
+    let collator = name_collator();
+    apps.sort_by(|a, b| collator.compare(&a.name, &b.name));
+    apps.into_iter().map(|a| a.bundle_id).collect()
+}
+
+/// Source folders whose apps a fresh layout groups into a folder of the same name
+const STARTER_FOLDERS: &[&str] = &["System", "Utilities"];
+
+/// Keep the stored layout in step with what is installed
+fn sync_layout_with_apps(apps: &[App]) {
+    let installed: Vec<String> = apps.iter().map(|a| a.bundle_id.clone()).collect();
+    let mut layout = layout_store().lock().unwrap();
+    let fresh = layout.pages.is_empty() && layout.folders.is_empty();
+    let mut changed = layout.sync_apps(&installed);
+    if fresh {
+        for name in STARTER_FOLDERS {
+            let bundle_ids: Vec<String> = apps
+                .iter()
+                .filter(|a| a.source_folder.as_deref() == Some(*name))
+                .map(|a| a.bundle_id.clone())
+                .collect();
+            if bundle_ids.is_empty() {
+                continue;
+            }
+            if let Err(e) = layout.create_folder(name, &bundle_ids) {
+                eprintln!("[Layout] Failed to create the {} folder: {}", name, e);
+            }
+        }
+    }
+    changed |= refresh_smart_folders(&mut layout, |_| {
+        let usage = usage_store().lock().unwrap();
+        apps.par_iter()
+            .map(|a| app_facts(&a.bundle_id, &a.name, &a.path, a.developer.clone(), a.tags.clone(), &usage))
+            .collect()
+    });
+
+    if changed {
+        if let Err(e) = layout.save(&active_profile_id()) {
+            eprintln!("[Layout] {}", e);
+        }
+    }
+}
+
+/// What smart folder rules are evaluated against for one app (reads the bundle from disk)
+fn app_facts(
+    bundle_id: &str,
+    name: &str,
+    path: &str,
+    developer: Option<String>,
+    tags: Vec<String>,
+    usage: &UsageStore,
+) -> AppFacts {
+    let app_path = Path::new(path);
+    let copyright = plist::Value::from_file(app_path.join("Contents/Info.plist"))
+        .ok()
+        .and_then(|v| {
+            v.as_dictionary()?
+                .get("NSHumanReadableCopyright")?
+                .as_string()
+                .map(String::from)
+        });
+
+    AppFacts {
+        bundle_id: bundle_id.to_string(),
+        name: name.to_string(),
+        path: path.to_string(),
+        tags,
+        developer,
+        copyright,
+        installed_at: installed_at(app_path),
+        archs: app_arch::executable_archs(app_path),
+        last_launched: usage.get(bundle_id).and_then(|u| u.last_launched),
+    }
+}
+
+/// When the bundle was created on disk (its modification time if that's unavailable)
+fn installed_at(app_path: &Path) -> Option<u64> {
+    fs::metadata(app_path)
+        .and_then(|m| m.created().or_else(|_| m.modified()))
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+/// Total size of the files in a bundle, in bytes
+fn bundle_size(app_path: &Path) -> u64 {
+    WalkDir::new(app_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Sort facts for every installed app that isn't hidden, by bundle ID; sizes are only
+/// measured when needed. Hidden apps get none, so they don't count towards their folder.
+fn installed_sort_facts(
+    hidden: &BTreeSet<String>,
+    with_size: bool,
+) -> HashMap<String, SortFacts> {
+    let mut metadata = scan_all_app_metadata();
+    metadata.retain(|m| !is_hidden(hidden, &m.bundle_id, &m.path));
+    let store = tag_store().lock().unwrap();
+    let usage = usage_store().lock().unwrap();
+    metadata
+        .par_iter()
+        .map(|m| {
+            let app_path = Path::new(&m.path);
+            let app_usage = usage.get(&m.bundle_id);
+            let facts = SortFacts {
+                name: m.name.clone(),
+                installed_at: installed_at(app_path),
+                last_launched: app_usage.and_then(|u| u.last_launched),
+                launch_count: app_usage.map(|u| u.launch_count).unwrap_or(0),
+                size: with_size.then(|| bundle_size(app_path)),
+                first_tag: store.tags_for(&m.bundle_id).into_iter().next(),
+            };
+            (m.bundle_id.clone(), facts)
+        })
+        .collect()
+}
+
+/// Facts for every installed app that isn't hidden, with tags from the tag store
+fn installed_app_facts(hidden: &BTreeSet<String>) -> Vec<AppFacts> {
+    let mut metadata = scan_all_app_metadata();
+    metadata.retain(|m| !is_hidden(hidden, &m.bundle_id, &m.path));
+    let store = tag_store().lock().unwrap();
+    let usage = usage_store().lock().unwrap();
+    metadata
+        .par_iter()
+        .map(|m| {
+            let tags = store.tags_for(&m.bundle_id);
+            app_facts(&m.bundle_id, &m.name, &m.path, m.developer.clone(), tags, &usage)
+        })
+        .collect()
+}
+
+/// Re-evaluate the smart folders, which never take in hidden apps. Facts are only gathered
+/// (given the layout's hidden bundle IDs) if the layout has any smart folders.
+fn refresh_smart_folders<F>(layout: &mut Layout, facts: F) -> bool
+where
+    F: FnOnce(&BTreeSet<String>) -> Vec<AppFacts>,
+{
+    if !layout.folders.iter().any(|f| f.rules.is_some()) {
+        return false;
+    }
+    let mut facts = facts(&layout.hidden);
+    facts.retain(|f| !is_hidden(&layout.hidden, &f.bundle_id, &f.path));
+    smart_folders::refresh(layout, &facts, usage::now_secs(), &name_collator())
+}
+
+/// Record freshly detected tags in the tag store and replace them with the effective tags
+fn apply_stored_tags(apps: &mut [App]) {
+    let mut store = tag_store().lock().unwrap();
+    let mut changed = false;
+
+    for app in apps.iter_mut() {
+        changed |= store.set_detected(&app.bundle_id, &app.tags);
+
+        // Finder tags show up next to the detected ones
+        let finder_names: Vec<String> = finder_tags::read_finder_tags(Path::new(&app.path))
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        changed |= store.set_finder_tags(&app.bundle_id, &app.path, &finder_names);
+
+        app.tags = store.tags_for(&app.bundle_id);
+    }
+
+    if changed {
+        if let Err(e) = store.save() {
+            eprintln!("[TagStore] {}", e);
+        }
+    }
+}
+
+/// Load app icons in parallel using rayon, emitting updates as they're ready
+#[tauri::command]
+async fn load_app_icons(app: tauri::AppHandle) -> Result<(), String> {
+    let app_metadata = scan_all_app_metadata();
 
     // Extract icons in parallel using rayon
     let icons: Vec<(String, Option<String>)> = app_metadata
         .par_iter()
         .map(|meta| {
-            let icon = extract_app_icon_for_path(&meta.actual_app_path);
+            let icon =
+                extract_app_icon_for_path(&meta.actual_app_path, meta.artwork_path.as_deref());
             (meta.bundle_id.clone(), icon)
         })
         .collect();
 
+    // Icons the user picked win over the bundle icons
+    let custom_icons = layout_store().lock().unwrap().custom_icons.clone();
+    let icons: Vec<(String, Option<String>)> = icons
+        .into_iter()
+        .map(|(bundle_id, icon)| {
+            let icon = custom_icons
+                .get(&bundle_id)
+                .and_then(|icon| custom_icons::data_url(icon))
+                .or(icon);
+            (bundle_id, icon)
+        })
+        .collect();
+
     // Emit icon updates in batches for efficiency
     let batch_size = 10;
     for chunk in icons.chunks(batch_size) {
@@ -256,8 +793,22 @@ fn get_installed_apps() -> Result<Vec<App>, String> {
     apps.sort_by(|a, b| a.bundle_id.cmp(&b.bundle_id));
     apps.dedup_by(|a, b| a.bundle_id == b.bundle_id);
 
+    {
+        let names = display_names().lock().unwrap();
+        for app in &mut apps {
+            if let Some(custom) = names.get(&app.bundle_id) {
+                app.original_name = Some(std::mem::replace(&mut app.name, custom.clone()));
+            }
+        }
+    }
+
     // Sort alphabetically by name
-    apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    let collator = name_collator();
+    apps.sort_by(|a, b| collator.compare(&a.name, &b.name));
+
+    apply_stored_tags(&mut apps);
+    apply_badges(&mut apps);
+    remove_hidden_apps(&mut apps);
 
     Ok(apps)
 }
@@ -390,11 +941,22 @@ fn parse_app_bundle(app_path: &Path, source_folder: Option<&str>) -> Option<App>
         return None;
     }
 
+    // App Store metadata of wrapped iOS apps (genre, developer, artwork)
+    let itunes = itunes_metadata::read_itunes_metadata(app_path).unwrap_or_default();
+
     // Get app icon (use actual_app_path for wrapped apps since icon is in inner bundle)
-    let icon = extract_app_icon(&actual_app_path, plist_dict);
+    let icon = extract_app_icon_with_artwork(
+        &actual_app_path,
+        plist_dict,
+        itunes.artwork_path.as_deref(),
+    );
 
-    // Detect tags from app category
-    let tags = detect_app_tags(plist_dict, &bundle_id, &name);
+    // Browser-installed web apps are categorized by site, everything else by app category
+    let web_app = web_apps::detect_web_app(&actual_app_path, plist_dict, &bundle_id);
+    let tags = match &web_app {
+        Some(info) => web_apps::detect_web_app_tags(info, &name),
+        None => detect_wrapped_app_tags(&itunes, plist_dict, &bundle_id, &name),
+    };
 
     Some(App {
         name,
@@ -403,6 +965,17 @@ fn parse_app_bundle(app_path: &Path, source_folder: Option<&str>) -> Option<App>
         icon,
         source_folder: source_folder.map(|s| s.to_string()),
         tags,
+        kind: if web_app.is_some() {
+            AppKind::WebApp
+        } else {
+            AppKind::App
+        },
+        web_app,
+        developer: itunes.artist,
+        genre: itunes.genre,
+        original_name: None,
+        is_new: false,
+        recently_updated: false,
     })
 }
 
@@ -485,8 +1058,21 @@ fn parse_app_bundle_fast(app_path: &Path, source_folder: Option<&str>) -> Option
         return None;
     }
 
-    // Detect tags from app category
-    let tags = detect_app_tags(plist_dict, &bundle_id, &name);
+    let version = plist_dict
+        .get("CFBundleShortVersionString")
+        .or_else(|| plist_dict.get("CFBundleVersion"))
+        .and_then(|v| v.as_string())
+        .map(|s| s.to_string());
+
+    // App Store metadata of wrapped iOS apps (genre, developer, artwork)
+    let itunes = itunes_metadata::read_itunes_metadata(app_path).unwrap_or_default();
+
+    // Browser-installed web apps are categorized by site, everything else by app category
+    let web_app = web_apps::detect_web_app(&actual_app_path, plist_dict, &bundle_id);
+    let tags = match &web_app {
+        Some(info) => web_apps::detect_web_app_tags(info, &name),
+        None => detect_wrapped_app_tags(&itunes, plist_dict, &bundle_id, &name),
+    };
 
     Some(AppMetadata {
         name,
@@ -495,11 +1081,17 @@ fn parse_app_bundle_fast(app_path: &Path, source_folder: Option<&str>) -> Option
         actual_app_path,
         source_folder: source_folder.map(|s| s.to_string()),
         tags,
+        web_app,
+        developer: itunes.artist,
+        genre: itunes.genre,
+        artwork_path: itunes.artwork_path,
+        version,
+        original_name: None,
     })
 }
 
 /// Extract icon for a given app path (used by parallel icon loading)
-fn extract_app_icon_for_path(app_path: &Path) -> Option<String> {
+fn extract_app_icon_for_path(app_path: &Path, artwork_path: Option<&Path>) -> Option<String> {
     // Find and read Info.plist
     let info_plist_path = if app_path.join("Contents/Info.plist").exists() {
         app_path.join("Contents/Info.plist")
@@ -512,26 +1104,85 @@ fn extract_app_icon_for_path(app_path: &Path) -> Option<String> {
     let plist_value = plist::Value::from_file(&info_plist_path).ok()?;
     let plist_dict = plist_value.as_dictionary()?;
 
+    extract_app_icon_with_artwork(app_path, plist_dict, artwork_path)
+}
+
+/// Wrapped iOS apps often ship only small PNGs in the inner bundle.
+/// Use the App Store artwork instead when the bundle has nothing big enough.
+fn extract_app_icon_with_artwork(
+    app_path: &Path,
+    plist_dict: &plist::Dictionary,
+    artwork_path: Option<&Path>,
+) -> Option<String> {
+    if let Some(artwork) = artwork_path {
+        // Kept apart from the bundle's own icon, which an earlier scan may have cached
+        // under the plain key before the artwork was considered
+        let cache_key = get_icon_cache_key(app_path).map(|key| format!("{}-artwork", key));
+
+        if let Some(ref key) = cache_key {
+            if let Some(cached) = load_cached_icon(key) {
+                return Some(cached);
+            }
+        }
+
+        if itunes_metadata::largest_bundle_png_size(app_path) < itunes_metadata::MIN_ICON_SIZE {
+            if let Some(png_data) = itunes_metadata::artwork_as_png(artwork) {
+                if let Some(ref key) = cache_key {
+                    save_icon_to_cache(key, &png_data);
+                }
+
+                let encoded =
+                    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &png_data);
+                return Some(format!("data:image/png;base64,{}", encoded));
+            }
+        }
+    }
+
     extract_app_icon(app_path, plist_dict)
 }
 
+/// The App Store genre of a wrapped iOS app is curated, so it wins over our heuristics
+fn detect_wrapped_app_tags(
+    itunes: &itunes_metadata::ItunesMetadata,
+    plist_dict: &plist::Dictionary,
+    bundle_id: &str,
+    name: &str,
+) -> Vec<String> {
+    if let Some(tag) = itunes
+        .genre
+        .as_deref()
+        .and_then(itunes_metadata::map_genre_to_tag)
+    {
+        return vec![tag.to_string()];
+    }
+
+    detect_app_tags(plist_dict, bundle_id, name)
+}
+
 fn detect_app_tags(plist_dict: &plist::Dictionary, bundle_id: &str, name: &str) -> Vec<String> {
     let mut tags = Vec::new();
 
-    // Priority 1: Bundle ID pattern matching (most specific - catches browsers, etc.)
+    // Priority 1: Declared URL schemes and document types (what the app says it handles;
+    // browsers are caught here by the http/https schemes they register)
+    if let Some(tag) = detect_tag_from_declared_types(plist_dict) {
+        tags.push(tag.to_string());
+        return tags;
+    }
+
+    // Priority 2: Bundle ID pattern matching (name heuristics for apps that declare nothing)
     if let Some(tag) = detect_tag_from_bundle_id(bundle_id) {
         tags.push(tag.to_string());
         return tags;
     }
 
-    // Priority 2: Well-known apps database (specific app names)
+    // Priority 3: Well-known apps database (specific app names)
     if let Some(tag) = detect_tag_from_app_name(name, bundle_id) {
         tags.push(tag.to_string());
         // println!("✅ Tag detected via app_name: {} (bundle: {}) → {}", name, bundle_id, tag);
         return tags;
     }
 
-    // Priority 3: LSApplicationCategoryType from macOS (fallback for general categorization)
+    // Priority 4: LSApplicationCategoryType from macOS (fallback for general categorization)
     if let Some(category) = plist_dict
         .get("LSApplicationCategoryType")
         .and_then(|v| v.as_string())
@@ -584,10 +1235,111 @@ fn map_macos_category_to_tag(category: &str) -> Option<&'static str> {
     }
 }
 
+/// URL schemes an app can claim in CFBundleURLTypes, mapped to the tag they imply
+const URL_SCHEME_TAGS: &[(&str, &str)] = &[
+    ("http", "browsers"),
+    ("https", "browsers"),
+    ("mailto", "social"),
+    ("message", "social"),
+    ("facetime", "social"),
+    ("webcal", "planning"),
+];
+
+/// Content types (UTIs) an app edits or exports, mapped to the tag they imply.
+/// Order matters: more specific types come before the generic ones they conform to.
+const CONTENT_TYPE_TAGS: &[(&str, &str)] = &[
+    ("public.source-code", "dev-tools"),
+    ("public.script", "dev-tools"),
+    ("public.shell-script", "dev-tools"),
+    ("public.image", "creativity"),
+    ("public.movie", "creativity"),
+    ("public.audio", "creativity"),
+    ("public.spreadsheet", "office"),
+    ("public.presentation", "office"),
+    ("org.openxmlformats.wordprocessingml.document", "office"),
+    ("com.microsoft.word.doc", "office"),
+    ("com.apple.iwork.pages.sffpages", "office"),
+    ("public.calendar-event", "planning"),
+    ("com.apple.ical.ics", "planning"),
+];
+
+/// Detect tag from what the app declares it handles: URL schemes (CFBundleURLTypes),
+/// edited document types (CFBundleDocumentTypes) and exported UTIs (UTExportedTypeDeclarations)
+fn detect_tag_from_declared_types(plist_dict: &plist::Dictionary) -> Option<&'static str> {
+    // URL schemes - a browser registers http/https, a mail client registers mailto
+    let url_schemes: Vec<String> = plist_dict
+        .get("CFBundleURLTypes")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.as_dictionary())
+        .filter_map(|t| t.get("CFBundleURLSchemes").and_then(|v| v.as_array()))
+        .flatten()
+        .filter_map(|s| s.as_string())
+        .map(|s| s.to_lowercase())
+        .collect();
+
+    for (scheme, tag) in URL_SCHEME_TAGS {
+        if url_schemes.iter().any(|s| s == scheme) {
+            return Some(*tag);
+        }
+    }
+
+    // Document types - only count types the app can edit, viewers (Quick Look, players)
+    // declare half the UTI tree and say nothing about what the app is for
+    let mut content_types: Vec<String> = plist_dict
+        .get("CFBundleDocumentTypes")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.as_dictionary())
+        .filter(|t| {
+            t.get("CFBundleTypeRole")
+                .and_then(|v| v.as_string())
+                .map(|role| role == "Editor")
+                .unwrap_or(false)
+        })
+        .filter_map(|t| t.get("LSItemContentTypes").and_then(|v| v.as_array()))
+        .flatten()
+        .filter_map(|s| s.as_string())
+        .map(|s| s.to_string())
+        .collect();
+
+    // Exported types - an app that defines its own format says what that format conforms to
+    content_types.extend(
+        plist_dict
+            .get("UTExportedTypeDeclarations")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|t| t.as_dictionary())
+            .filter_map(|t| t.get("UTTypeConformsTo"))
+            .flat_map(|v| match v {
+                // UTTypeConformsTo may be a single string or an array of strings
+                plist::Value::String(s) => vec![s.clone()],
+                plist::Value::Array(items) => items
+                    .iter()
+                    .filter_map(|s| s.as_string())
+                    .map(|s| s.to_string())
+                    .collect(),
+                _ => Vec::new(),
+            }),
+    );
+
+    for (content_type, tag) in CONTENT_TYPE_TAGS {
+        if content_types.iter().any(|t| t == content_type) {
+            return Some(*tag);
+        }
+    }
+
+    None
+}
+
 fn detect_tag_from_bundle_id(bundle_id: &str) -> Option<&'static str> {
     let bundle_lower = bundle_id.to_lowercase();
 
     // Exclude Chrome/Edge PWAs (Progressive Web Apps) - these are NOT browsers
+    // (the scanner normally catches them first via web_apps::detect_web_app)
     if bundle_lower.contains(".chrome.app.") || bundle_lower.contains(".edge.app.") {
         return None; // Let other detection methods handle PWAs
     }
@@ -959,18 +1711,104 @@ fn extract_icns_as_base64(icon_path: &Path, cache_key: Option<&str>) -> Option<S
     Some(format!("data:image/png;base64,{}", encoded))
 }
 
+/// Run `open` on the blocking thread pool, as waiting for it would hold up the async runtime
+async fn run_open(args: Vec<String>, app_path: &str) -> Result<(), LaunchError> {
+    let path = app_path.to_string();
+    tauri::async_runtime::spawn_blocking(move || launch_error::run_open(&args, &path))
+        .await
+        .unwrap_or_else(|e| {
+            let message = format!("Launch task failed: {}", e);
+            Err(LaunchError::new(LaunchErrorKind::Unknown, app_path, message))
+        })
+}
+
+/// Launch an app, waiting for `open` to report whether it worked
 #[tauri::command]
-fn launch_app(app_path: String) -> Result<(), String> {
-    use std::process::Command;
+async fn launch_app(app_path: String) -> Result<(), LaunchError> {
+    run_open(vec![app_path.clone()], &app_path).await?;
+    record_launch(&app_path);
+    Ok(())
+}
 
-    Command::new("open")
-        .arg(&app_path)
-        .spawn()
-        .map_err(|e| format!("Failed to launch app: {}", e))?;
+/// Launch with arguments, environment, files and `open` flags, given directly or as
+/// the name of one of the app's saved presets
+#[tauri::command]
+async fn launch_app_with_options(
+    app_path: String,
+    options: Option<LaunchOptions>,
+    preset: Option<String>,
+) -> Result<(), LaunchError> {
+    let invalid =
+        |message: String| LaunchError::new(LaunchErrorKind::InvalidOptions, &app_path, message);
+    let options = match (options, preset) {
+        (Some(options), None) => options,
+        (None, Some(preset)) => {
+            let Some(metadata) = parse_app_bundle_fast(Path::new(&app_path), None) else {
+                // Without a bundle ID there are no presets to look up
+                let kind = if Path::new(&app_path).exists() {
+                    LaunchErrorKind::InvalidOptions
+                } else {
+                    LaunchErrorKind::MissingBundle
+                };
+                let message = format!("Failed to read the app bundle at {}", app_path);
+                return Err(LaunchError::new(kind, &app_path, message));
+            };
+            launch_presets()
+                .lock()
+                .unwrap()
+                .get(&metadata.bundle_id, &preset)
+                .map(|p| p.options.clone())
+                .ok_or_else(|| invalid(format!("Launch preset not found: {}", preset)))?
+        }
+        (None, None) => LaunchOptions::default(),
+        (Some(_), Some(_)) => {
+            return Err(invalid("Pass either options or a preset, not both".to_string()))
+        }
+    };
+    options.validate().map_err(invalid)?;
 
+    run_open(options.open_args(&app_path), &app_path).await?;
+    record_launch(&app_path);
     Ok(())
 }
 
+#[tauri::command]
+fn get_launch_presets(bundle_id: String) -> Result<Vec<LaunchPreset>, String> {
+    Ok(launch_presets().lock().unwrap().for_app(&bundle_id))
+}
+
+/// Save a preset for an app, replacing its preset with the same name
+#[tauri::command]
+fn save_launch_preset(
+    bundle_id: String,
+    preset: LaunchPreset,
+) -> Result<Vec<LaunchPreset>, String> {
+    let mut presets = launch_presets().lock().unwrap();
+    presets.set(&bundle_id, preset)?;
+    presets.save()?;
+    Ok(presets.for_app(&bundle_id))
+}
+
+#[tauri::command]
+fn delete_launch_preset(bundle_id: String, name: String) -> Result<Vec<LaunchPreset>, String> {
+    let mut presets = launch_presets().lock().unwrap();
+    presets.remove(&bundle_id, &name)?;
+    presets.save()?;
+    Ok(presets.for_app(&bundle_id))
+}
+
+/// Count a launch for usage-based rules and sorting
+fn record_launch(app_path: &str) {
+    let Some(metadata) = parse_app_bundle_fast(Path::new(app_path), None) else {
+        return;
+    };
+    let mut usage = usage_store().lock().unwrap();
+    usage.record_launch(&metadata.bundle_id);
+    if let Err(e) = usage.save() {
+        eprintln!("[Usage] {}", e);
+    }
+}
+
 #[tauri::command]
 fn move_app_to_trash(app_path: String) -> Result<(), String> {
     trash::delete(&app_path).map_err(|e| format!("Failed to move app to trash: {}", e))
@@ -989,8 +1827,1318 @@ fn reveal_in_finder(app_path: String) -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+fn get_custom_tags() -> Result<Vec<CustomTag>, String> {
+    Ok(tag_store().lock().unwrap().custom_tags().to_vec())
+}
+
+#[tauri::command]
+fn create_tag(label: String, icon_name: String) -> Result<CustomTag, String> {
+    let mut store = tag_store().lock().unwrap();
+    let tag = store.create_tag(&label, &icon_name)?;
+    store.save()?;
+    Ok(tag)
+}
+
+#[tauri::command]
+fn rename_tag(key: String, label: String) -> Result<(), String> {
+    let mut store = tag_store().lock().unwrap();
+    let affected = store.apps_with_tag(&key);
+    let previous_label = store.custom_tag_label(&key);
+    store.rename_tag(&key, &label)?;
+    store.save()?;
+    for bundle_id in &affected {
+        write_back_finder_tags(&mut store, bundle_id, previous_label.as_slice());
+    }
+    store.save()
+}
+
+#[tauri::command]
+fn delete_tag(key: String) -> Result<(), String> {
+    let mut store = tag_store().lock().unwrap();
+    let affected = store.apps_with_tag(&key);
+    let previous_label = store.custom_tag_label(&key);
+    store.delete_tag(&key)?;
+    store.save()?;
+    for bundle_id in &affected {
+        write_back_finder_tags(&mut store, bundle_id, previous_label.as_slice());
+    }
+    store.save()
+}
+
+/// Delete all custom tags and manual tag changes (Finder tags are written back if sync is on)
+#[tauri::command]
+fn reset_tags() -> Result<(), String> {
+    let mut store = tag_store().lock().unwrap();
+    let previous_labels = store.managed_finder_names();
+    let custom_keys: Vec<String> = store.custom_tags().iter().map(|t| t.key.clone()).collect();
+    let affected: Vec<String> = store
+        .assignments()
+        .into_iter()
+        .filter(|(_, tags)| tags.iter().any(|t| custom_keys.contains(t)))
+        .map(|(bundle_id, _)| bundle_id)
+        .collect();
+    store.reset();
+    store.save()?;
+    for bundle_id in &affected {
+        write_back_finder_tags(&mut store, bundle_id, &previous_labels);
+    }
+    store.save()
+}
+
+#[tauri::command]
+fn assign_tag(bundle_id: String, tag_key: String) -> Result<Vec<String>, String> {
+    let mut store = tag_store().lock().unwrap();
+    store.assign(&bundle_id, &tag_key)?;
+    store.save()?;
+    write_back_finder_tags(&mut store, &bundle_id, &[]);
+    store.save()?;
+    Ok(store.tags_for(&bundle_id))
+}
+
+#[tauri::command]
+fn unassign_tag(bundle_id: String, tag_key: String) -> Result<Vec<String>, String> {
+    let mut store = tag_store().lock().unwrap();
+    store.unassign(&bundle_id, &tag_key);
+    store.save()?;
+    write_back_finder_tags(&mut store, &bundle_id, &[]);
+    store.save()?;
+    Ok(store.tags_for(&bundle_id))
+}
+
+/// If Finder sync is on, write the custom tags of an app back to its Finder tags.
+/// `old_labels` are labels that were managed before a rename or delete.
+/// Best effort: bundles we can't write to (e.g. in /System/Applications) only log.
+fn write_back_finder_tags(store: &mut TagStore, bundle_id: &str, old_labels: &[String]) {
+    if !store.finder_sync_enabled() {
+        return;
+    }
+
+    // Apps we haven't scanned yet have no known path - nothing to write to
+    let Some((path, names)) = store.finder_names_for(bundle_id) else {
+        return;
+    };
+
+    let mut managed = store.managed_finder_names();
+    managed.extend_from_slice(old_labels);
+    match finder_tags::sync_finder_tag_names(Path::new(&path), &names, &managed) {
+        Ok(finder_names) => {
+            store.set_finder_tags(bundle_id, &path, &finder_names);
+        }
+        Err(e) => eprintln!("[FinderTags] {}", e),
+    }
+}
+
+#[tauri::command]
+fn get_finder_tag_sync() -> Result<bool, String> {
+    Ok(tag_store().lock().unwrap().finder_sync_enabled())
+}
+
+/// Enable or disable writing Launchpad tag changes back to Finder tags
+#[tauri::command]
+fn set_finder_tag_sync(enabled: bool) -> Result<(), String> {
+    let mut store = tag_store().lock().unwrap();
+    store.set_finder_sync(enabled);
+    store.save()
+}
+
+/// Effective tags for every known app (bundle ID -> tag keys)
+#[tauri::command]
+fn get_tag_assignments() -> Result<BTreeMap<String, Vec<String>>, String> {
+    Ok(tag_store().lock().unwrap().assignments())
+}
+
+#[tauri::command]
+fn get_apps_with_tag(tag_key: String) -> Result<Vec<String>, String> {
+    Ok(tag_store().lock().unwrap().apps_with_tag(&tag_key))
+}
+
+#[tauri::command]
+fn get_tag_counts() -> Result<BTreeMap<String, usize>, String> {
+    Ok(tag_store().lock().unwrap().tag_counts())
+}
+
+/// One-time migration of the tag data the frontend kept in localStorage.
+/// Returns false if it was already imported.
+#[tauri::command]
+fn import_legacy_tags(
+    assignments: BTreeMap<String, Vec<String>>,
+    custom_tags: Vec<CustomTag>,
+) -> Result<bool, String> {
+    let mut store = tag_store().lock().unwrap();
+    let imported = store.import_legacy(assignments, custom_tags);
+    if imported {
+        store.save()?;
+    }
+    Ok(imported)
+}
+
+/// The layout as shown: hidden apps keep their place in the stored layout but are left out
+#[tauri::command]
+fn get_layout() -> Result<Layout, String> {
+    let paths = inventory_paths();
+    let mut layout = layout_store().lock().unwrap().clone();
+    let hidden: Vec<String> = layout
+        .all_bundle_ids()
+        .into_iter()
+        .filter(|id| {
+            let path = paths.get(id).map(String::as_str).unwrap_or_default();
+            is_hidden(&layout.hidden, id, path)
+        })
+        .collect();
+    layout.apply_app_changes(&[], &hidden);
+    Ok(layout)
+}
+
+/// Apply a change to the layout, persist it, record an undo step and notify the frontend.
+/// Nothing is saved if the change fails.
+fn update_layout<T, F>(app: &tauri::AppHandle, change: F) -> Result<T, String>
+where
+    F: FnOnce(&mut Layout) -> Result<T, String>,
+{
+    let mut layout = layout_store().lock().unwrap();
+    update_locked_layout(app, &mut layout, change)
+}
+
+/// `update_layout` for callers already holding the layout store lock
+fn update_locked_layout<T, F>(
+    app: &tauri::AppHandle,
+    layout: &mut Layout,
+    change: F,
+) -> Result<T, String>
+where
+    F: FnOnce(&mut Layout) -> Result<T, String>,
+{
+    let mut updated = layout.clone();
+    let result = change(&mut updated)?;
+
+    if updated != *layout {
+        updated.save(&active_profile_id())?;
+        let previous = std::mem::replace(layout, updated);
+
+        let mut history = undo_history().lock().unwrap();
+        history.record(previous);
+        if let Err(e) = history.save() {
+            eprintln!("[LayoutHistory] {}", e);
+        }
+
+        publish_layout(layout);
+        let _ = app.emit("layout-changed", layout.clone());
+    }
+
+    Ok(result)
+}
+
+/// Keep a snapshot of the current layout before an operation that rewrites much of it
+fn snapshot_layout(reason: SnapshotReason) {
+    let layout = layout_store().lock().unwrap().clone();
+    if let Err(e) = layout_history::create_snapshot(&active_profile_id(), &layout, reason, None) {
+        eprintln!("[LayoutHistory] Failed to snapshot layout: {}", e);
+    }
+}
+
+/// Move through the undo history. `step` gets the current layout and returns the one to show.
+fn step_layout_history<F>(app: &tauri::AppHandle, step: F, empty: &str) -> Result<UndoState, String>
+where
+    F: FnOnce(&mut UndoHistory, Layout) -> Option<Layout>,
+{
+    let mut layout = layout_store().lock().unwrap();
+    let mut history = undo_history().lock().unwrap();
+
+    let mut stepped = history.clone();
+    let next = step(&mut stepped, layout.clone()).ok_or(empty)?;
+    next.save(&active_profile_id())?;
+    *layout = next;
+    *history = stepped;
+    if let Err(e) = history.save() {
+        eprintln!("[LayoutHistory] {}", e);
+    }
+
+    publish_layout(&layout);
+    let _ = app.emit("layout-changed", layout.clone());
+    Ok(history.state())
+}
+
+/// Publish the layout to the sync folder, if sync is on
+fn publish_layout(layout: &Layout) {
+    let profile_id = active_profile_id();
+    if let Err(e) = layout_sync().lock().unwrap().publish(&profile_id, layout) {
+        eprintln!("[LayoutSync] {}", e);
+    }
+}
+
+/// Merge the layouts other devices published into ours (as one undoable change)
+fn pull_synced_layouts(app: &tauri::AppHandle) -> Result<SyncReport, String> {
+    let profile_id = active_profile_id();
+    let report = update_layout(app, |layout| {
+        layout_sync().lock().unwrap().pull(&profile_id, layout)
+    })?;
+
+    if !report.conflicts.is_empty() {
+        let _ = app.emit("layout-sync-conflicts", report.clone());
+    }
+    Ok(report)
+}
+
+/// (Re)start watching the sync folder, or stop if sync is off
+fn restart_sync_watcher(app: &tauri::AppHandle) -> Result<(), String> {
+    let mut watcher = SYNC_WATCHER.lock().unwrap();
+    *watcher = None;
+
+    let (watch_dir, device_id) = {
+        let sync = layout_sync().lock().unwrap();
+        (sync.watch_dir(), sync.device_id().to_string())
+    };
+    let Some(watch_dir) = watch_dir else {
+        return Ok(());
+    };
+
+    let app = app.clone();
+    *watcher = Some(SyncWatcher::start(&watch_dir, &device_id, move || {
+        if let Err(e) = pull_synced_layouts(&app) {
+            eprintln!("[LayoutSync] {}", e);
+        }
+    })?);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_layout_sync() -> Result<SyncStatus, String> {
+    Ok(layout_sync().lock().unwrap().status())
+}
+
+/// Sync the layout through a folder shared between machines (None turns sync off).
+/// Other devices' layouts are merged in first, then ours is published.
+#[tauri::command]
+fn set_layout_sync_directory(
+    app: tauri::AppHandle,
+    directory: Option<String>,
+) -> Result<SyncReport, String> {
+    {
+        let mut sync = layout_sync().lock().unwrap();
+        sync.set_directory(directory.map(PathBuf::from))?;
+        sync.save()?;
+    }
+    restart_sync_watcher(&app)?;
+
+    if layout_sync().lock().unwrap().watch_dir().is_none() {
+        return Ok(SyncReport::default());
+    }
+    sync_layout_now(app)
+}
+
+#[tauri::command]
+fn set_sync_device_name(name: String) -> Result<(), String> {
+    let mut sync = layout_sync().lock().unwrap();
+    sync.set_device_name(&name)?;
+    sync.save()?;
+    drop(sync);
+
+    let layout = layout_store().lock().unwrap().clone();
+    publish_layout(&layout);
+    Ok(())
+}
+
+/// Merge other devices' layouts now and publish ours
+#[tauri::command]
+fn sync_layout_now(app: tauri::AppHandle) -> Result<SyncReport, String> {
+    let report = pull_synced_layouts(&app)?;
+
+    // Publish even when the merge changed nothing, e.g. on first sync
+    let layout = layout_store().lock().unwrap().clone();
+    publish_layout(&layout);
+    Ok(report)
+}
+
+/// Add a smart folder at the end of the layout and fill it from its rules
+#[tauri::command]
+fn create_smart_folder(
+    app: tauri::AppHandle,
+    name: String,
+    rules: SmartRules,
+) -> Result<Folder, String> {
+    let hidden = layout_store().lock().unwrap().hidden.clone();
+    let facts = installed_app_facts(&hidden);
+    update_layout(&app, |layout| {
+        let folder = smart_folders::create_smart_folder(layout, &name, rules)?;
+        refresh_smart_folders(layout, |_| facts);
+        layout
+            .folder(&folder.id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown folder: {}", folder.id))
+    })
+}
+
+/// Change a folder's rules; None turns a smart folder into a regular one keeping its apps
+#[tauri::command]
+fn set_smart_folder_rules(
+    app: tauri::AppHandle,
+    folder_id: String,
+    rules: Option<SmartRules>,
+) -> Result<(), String> {
+    if let Some(ref rules) = rules {
+        rules.validate()?;
+    }
+    let hidden = layout_store().lock().unwrap().hidden.clone();
+    let facts = installed_app_facts(&hidden);
+    update_layout(&app, |layout| {
+        layout
+            .folders
+            .iter_mut()
+            .find(|f| f.id == folder_id)
+            .ok_or_else(|| format!("Unknown folder: {}", folder_id))?
+            .rules = rules;
+        refresh_smart_folders(layout, |_| facts);
+        Ok(())
+    })
+}
+
+/// Re-evaluate the smart folders now (they are also refreshed after every scan)
+#[tauri::command]
+fn refresh_smart_folders_now(app: tauri::AppHandle) -> Result<(), String> {
+    let hidden = layout_store().lock().unwrap().hidden.clone();
+    let facts = installed_app_facts(&hidden);
+    update_layout(&app, |layout| {
+        refresh_smart_folders(layout, |_| facts);
+        Ok(())
+    })
+}
+
+/// Propose a layout with one folder per tag, leaving hidden apps out. Returns the preview
+/// without changing anything unless `apply` is set along with the token of the preview being
+/// applied; if the layout changed since, nothing is applied. Applying snapshots the current
+/// layout first and can be undone.
+#[tauri::command]
+fn auto_organize(
+    app: tauri::AppHandle,
+    options: Option<OrganizeOptions>,
+    apply: bool,
+    token: Option<String>,
+) -> Result<OrganizePreview, String> {
+    const CHANGED: &str = "The layout changed since the preview";
+
+    let options = options.unwrap_or_default();
+    let current = layout_store().lock().unwrap().clone();
+    let mut metadata = scan_all_app_metadata();
+    metadata.retain(|m| !is_hidden(&current.hidden, &m.bundle_id, &m.path));
+    let (apps, tag_labels) = {
+        let store = tag_store().lock().unwrap();
+        let apps: Vec<OrganizeApp> = metadata
+            .into_iter()
+            .map(|m| OrganizeApp {
+                tags: store.tags_for(&m.bundle_id),
+                bundle_id: m.bundle_id,
+                name: m.name,
+            })
+            .collect();
+        let labels: BTreeMap<String, String> = store
+            .custom_tags()
+            .iter()
+            .map(|t| (t.key.clone(), t.label.clone()))
+            .collect();
+        (apps, labels)
+    };
+
+    let (proposed, folders) =
+        auto_organize::organize(&current, &apps, &options, &tag_labels, &name_collator());
+    let diff = current.diff(&proposed);
+    let preview_token = auto_organize::preview_token(&current, &proposed);
+
+    if apply {
+        if token.as_deref() != Some(preview_token.as_str()) {
+            return Err(CHANGED.to_string());
+        }
+        snapshot_layout(SnapshotReason::BeforeOrganize);
+        update_layout(&app, |layout| {
+            if *layout != current {
+                return Err(CHANGED.to_string());
+            }
+            *layout = proposed;
+            Ok(())
+        })?;
+    }
+
+    Ok(OrganizePreview {
+        folders,
+        diff,
+        applied: apply,
+        token: preview_token,
+    })
+}
+
+/// Reorder a page, a folder or the whole layout and persist the new order
+#[tauri::command]
+fn sort_layout(
+    app: tauri::AppHandle,
+    scope: SortScope,
+    key: SortKey,
+    reverse: Option<bool>,
+) -> Result<(), String> {
+    let hidden = layout_store().lock().unwrap().hidden.clone();
+    let facts = installed_sort_facts(&hidden, key.needs_size());
+    let collator = name_collator();
+    update_layout(&app, |layout| {
+        let reverse = reverse.unwrap_or(false);
+        sorting::sort_layout(layout, &scope, key, reverse, &facts, &collator)
+    })
+}
+
+#[tauri::command]
+fn undo_layout(app: tauri::AppHandle) -> Result<UndoState, String> {
+    step_layout_history(&app, |history, current| history.undo(current), "Nothing to undo")
+}
+
+#[tauri::command]
+fn redo_layout(app: tauri::AppHandle) -> Result<UndoState, String> {
+    step_layout_history(&app, |history, current| history.redo(current), "Nothing to redo")
+}
+
+#[tauri::command]
+fn get_undo_state() -> Result<UndoState, String> {
+    Ok(undo_history().lock().unwrap().state())
+}
+
+#[tauri::command]
+fn create_layout_snapshot(label: Option<String>) -> Result<SnapshotInfo, String> {
+    let layout = layout_store().lock().unwrap().clone();
+    layout_history::create_snapshot(&active_profile_id(), &layout, SnapshotReason::Manual, label)?
+        .ok_or_else(|| "Snapshot was not created".to_string())
+}
+
+/// Snapshots of the active profile, newest first
+#[tauri::command]
+fn list_layout_snapshots() -> Result<Vec<SnapshotInfo>, String> {
+    layout_history::list_snapshots(&active_profile_id())
+}
+
+/// What changed in the current layout since the snapshot was taken
+#[tauri::command]
+fn diff_layout_snapshot(snapshot_id: String) -> Result<LayoutDiff, String> {
+    let snapshot = layout_history::load_snapshot(&active_profile_id(), &snapshot_id)?;
+    let layout = layout_store().lock().unwrap();
+    Ok(snapshot.layout.diff(&layout))
+}
+
+/// Go back to a snapshot. The current layout is snapshotted first, and the restore can be undone.
+#[tauri::command]
+fn restore_layout_snapshot(app: tauri::AppHandle, snapshot_id: String) -> Result<(), String> {
+    let snapshot = layout_history::load_snapshot(&active_profile_id(), &snapshot_id)?;
+    snapshot_layout(SnapshotReason::BeforeRestore);
+
+    update_layout(&app, |layout| {
+        *layout = Layout {
+            legacy_imported: layout.legacy_imported,
+            ..snapshot.layout
+        };
+        Ok(())
+    })
+}
+
+#[tauri::command]
+fn delete_layout_snapshot(snapshot_id: String) -> Result<(), String> {
+    layout_history::delete_snapshot(&active_profile_id(), &snapshot_id)
+}
+
+/// Drop all folders and positions and start over from the alphabetical grid.
+/// Hidden apps and custom icons are kept. A snapshot is taken first.
+#[tauri::command]
+fn reset_layout(app: tauri::AppHandle) -> Result<(), String> {
+    let installed = installed_bundle_ids();
+
+    snapshot_layout(SnapshotReason::BeforeReset);
+
+    update_layout(&app, |layout| {
+        layout.pages.clear();
+        layout.folders.clear();
+        layout.sync_apps(&installed);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+fn move_layout_item(
+    app: tauri::AppHandle,
+    item: ItemRef,
+    page: usize,
+    index: usize,
+) -> Result<(), String> {
+    update_layout(&app, |layout| layout.move_item(item, page, index))
+}
+
+#[tauri::command]
+fn move_app_to_folder(
+    app: tauri::AppHandle,
+    bundle_id: String,
+    folder_id: String,
+    index: Option<usize>,
+) -> Result<(), String> {
+    update_layout(&app, |layout| layout.move_to_folder(&bundle_id, &folder_id, index))
+}
+
+#[tauri::command]
+fn create_folder(
+    app: tauri::AppHandle,
+    name: String,
+    bundle_ids: Vec<String>,
+) -> Result<Folder, String> {
+    update_layout(&app, |layout| layout.create_folder(&name, &bundle_ids))
+}
+
+#[tauri::command]
+fn rename_folder(app: tauri::AppHandle, folder_id: String, name: String) -> Result<(), String> {
+    update_layout(&app, |layout| layout.rename_folder(&folder_id, &name))
+}
+
+#[tauri::command]
+fn delete_folder(app: tauri::AppHandle, folder_id: String) -> Result<(), String> {
+    snapshot_layout(SnapshotReason::BeforeDelete);
+    update_layout(&app, |layout| layout.delete_folder(&folder_id))
+}
+
+/// Drop one item onto another (creates or extends a folder). Returns the folder ID.
+#[tauri::command]
+fn merge_layout_items(
+    app: tauri::AppHandle,
+    source: ItemRef,
+    target: ItemRef,
+) -> Result<String, String> {
+    update_layout(&app, |layout| layout.merge(&source, &target))
+}
+
+#[tauri::command]
+fn set_layout_page_size(app: tauri::AppHandle, page_size: usize) -> Result<(), String> {
+    update_layout(&app, |layout| layout.set_page_size(page_size))
+}
+
+/// One-time migration of the layout the frontend kept in localStorage
+/// (`launchpad-folders` and `launchpad-item-order`), paginated with the active profile's grid.
+/// Returns false (without rescanning) if it was already imported.
+#[tauri::command]
+fn import_legacy_layout(
+    app: tauri::AppHandle,
+    folders: Vec<LegacyFolder>,
+    item_order: HashMap<String, i64>,
+) -> Result<bool, String> {
+    if layout_store().lock().unwrap().legacy_imported {
+        return Ok(false);
+    }
+    let installed = installed_bundle_ids();
+
+    snapshot_layout(SnapshotReason::BeforeImport);
+    update_layout(&app, |layout| {
+        Ok(layout.import_legacy(folders, item_order, &installed))
+    })
+}
+
+/// Rebuild the layout from the system Launchpad database (default location unless
+/// `db_path` is given). With `apply` the result replaces the current layout.
+#[tauri::command]
+fn import_launchpad_database(
+    app: tauri::AppHandle,
+    db_path: Option<String>,
+    apply: bool,
+) -> Result<launchpad_import::LaunchpadImport, String> {
+    let db_path = match db_path {
+        Some(path) => PathBuf::from(path),
+        None => launchpad_import::default_db_path()
+            .ok_or("System Launchpad database not found")?,
+    };
+
+    let mut apps = scan_all_app_metadata();
+    let collator = name_collator();
+    apps.sort_by(|a, b| collator.compare(&a.name, &b.name));
+    let installed: Vec<launchpad_import::InstalledApp> = apps
+        .into_iter()
+        .map(|a| launchpad_import::InstalledApp {
+            bundle_id: a.bundle_id,
+            name: a.name,
+        })
+        .collect();
+
+    let page_size = layout_store().lock().unwrap().page_size;
+    let import = launchpad_import::import_launchpad_db(&db_path, &installed, page_size)?;
+
+    if apply {
+        snapshot_layout(SnapshotReason::BeforeImport);
+        update_layout(&app, |layout| {
+            *layout = Layout {
+                hidden: std::mem::take(&mut layout.hidden),
+                favorites: std::mem::take(&mut layout.favorites),
+                custom_icons: std::mem::take(&mut layout.custom_icons),
+                legacy_imported: layout.legacy_imported,
+                ..import.layout.clone()
+            };
+            Ok(())
+        })?;
+    }
+
+    Ok(import)
+}
+
+/// Put the apps pinned in the Dock first on page one, in Dock order.
+/// Reads ~/Library/Preferences/com.apple.dock.plist unless `plist_path` is given.
+#[tauri::command]
+fn seed_layout_from_dock(
+    app: tauri::AppHandle,
+    plist_path: Option<String>,
+) -> Result<dock_seed::DockSeedResult, String> {
+    let plist_path = match plist_path {
+        Some(path) => PathBuf::from(path),
+        None => dock_seed::default_dock_plist_path().ok_or("Dock preferences not found")?,
+    };
+
+    let dock_apps = dock_seed::read_dock_apps(&plist_path)?;
+    let installed: Vec<(String, String)> = scan_all_app_metadata()
+        .into_iter()
+        .map(|a| (a.bundle_id, a.path))
+        .collect();
+    let result = dock_seed::resolve_dock_apps(dock_apps, &installed);
+
+    snapshot_layout(SnapshotReason::BeforeImport);
+    update_layout(&app, |layout| {
+        layout.pin_to_first_page(&result.placed);
+        Ok(())
+    })?;
+
+    Ok(result)
+}
+
+/// An install or update with the app's current name and path
+#[derive(Debug, Clone, Serialize)]
+struct RecentAppInfo {
+    #[serde(flatten)]
+    recent: RecentApp,
+    name: String,
+    path: String,
+}
+
+/// Apps installed or updated within `days` days (the badge period by default), newest first
+#[tauri::command]
+fn get_recent_apps(days: Option<u64>) -> Result<Vec<RecentAppInfo>, String> {
+    let days = days.unwrap_or_else(|| settings().lock().unwrap().new_badge_days);
+    let metadata: HashMap<String, AppMetadata> = scan_all_app_metadata()
+        .into_iter()
+        .map(|m| (m.bundle_id.clone(), m))
+        .collect();
+
+    let usage = usage_store().lock().unwrap();
+    let recent = install_history().lock().unwrap().recent(days, usage::now_secs(), |id| {
+        usage.get(id).and_then(|u| u.last_launched)
+    });
+
+    // Apps that were uninstalled since are left out
+    Ok(recent
+        .into_iter()
+        .filter_map(|recent| {
+            let m = metadata.get(&recent.bundle_id)?;
+            Some(RecentAppInfo {
+                name: m.name.clone(),
+                path: m.path.clone(),
+                recent,
+            })
+        })
+        .collect())
+}
+
+/// Display name overrides by bundle ID
+#[tauri::command]
+fn get_display_names() -> Result<BTreeMap<String, String>, String> {
+    Ok(display_names().lock().unwrap().all().clone())
+}
+
+/// Show an app under a name of the user's choosing (None restores the bundle's name)
+#[tauri::command]
+fn set_display_name(
+    app: tauri::AppHandle,
+    bundle_id: String,
+    name: Option<String>,
+) -> Result<(), String> {
+    let names = {
+        let mut names = display_names().lock().unwrap();
+        match name {
+            Some(name) => names.set(&bundle_id, &name)?,
+            None => {
+                if !names.remove(&bundle_id) {
+                    return Ok(());
+                }
+            }
+        }
+        names.save()?;
+        names.all().clone()
+    };
+    let _ = app.emit("display-names-changed", names);
+    Ok(())
+}
+
+/// Use an image file as the icon of an app (None restores the bundle icon)
+#[tauri::command]
+fn set_custom_icon(
+    app: tauri::AppHandle,
+    bundle_id: String,
+    image_path: Option<String>,
+) -> Result<(), String> {
+    let icon = match image_path {
+        Some(path) => {
+            let png_data = itunes_metadata::artwork_as_png(Path::new(&path))
+                .ok_or_else(|| format!("Failed to read image: {}", path))?;
+            Some(custom_icons::store(&png_data)?)
+        }
+        None => None,
+    };
+
+    update_layout(&app, |layout| {
+        match icon {
+            Some(icon) => layout.custom_icons.insert(bundle_id, icon),
+            None => layout.custom_icons.remove(&bundle_id),
+        };
+        Ok(())
+    })
+}
+
+/// A pinned app, with what a companion surface (menu bar, CLI) needs to show and launch it
+#[derive(Debug, Clone, Serialize)]
+struct FavoriteApp {
+    bundle_id: String,
+    name: String,
+    path: String,
+}
+
+/// Favorites of the current profile in order (apps that are hidden or can't be found are
+/// left out)
+#[tauri::command]
+fn get_favorites() -> Result<Vec<FavoriteApp>, String> {
+    let (favorites, hidden) = {
+        let layout = layout_store().lock().unwrap();
+        (layout.favorites.clone(), layout.hidden.clone())
+    };
+    let mut metadata: HashMap<String, AppMetadata> = scan_all_app_metadata()
+        .into_iter()
+        .filter(|m| !is_hidden(&hidden, &m.bundle_id, &m.path))
+        .map(|m| (m.bundle_id.clone(), m))
+        .collect();
+
+    Ok(favorites
+        .iter()
+        .filter_map(|bundle_id| metadata.remove(bundle_id))
+        .map(|m| FavoriteApp {
+            bundle_id: m.bundle_id,
+            name: m.name,
+            path: m.path,
+        })
+        .collect())
+}
+
+/// Pin an app to the favorites (None appends), or move it if it's already pinned
+#[tauri::command]
+fn pin_favorite(
+    app: tauri::AppHandle,
+    bundle_id: String,
+    index: Option<usize>,
+) -> Result<(), String> {
+    update_layout(&app, |layout| layout.pin_favorite(&bundle_id, index))
+}
+
+#[tauri::command]
+fn unpin_favorite(app: tauri::AppHandle, bundle_id: String) -> Result<(), String> {
+    update_layout(&app, |layout| layout.unpin_favorite(&bundle_id))
+}
+
+#[tauri::command]
+fn reorder_favorites(app: tauri::AppHandle, bundle_ids: Vec<String>) -> Result<(), String> {
+    update_layout(&app, |layout| layout.reorder_favorites(&bundle_ids))
+}
+
+/// Installed apps that are hidden, and the path rules
+fn hidden_apps() -> HiddenApps {
+    let mut metadata = scan_all_app_metadata();
+    let collator = name_collator();
+    metadata.sort_by(|a, b| collator.compare(&a.name, &b.name));
+
+    let layout = layout_store().lock().unwrap();
+    let rules = hidden_rules().lock().unwrap();
+    let apps = metadata
+        .into_iter()
+        .filter_map(|m| {
+            let by_bundle_id = layout.hidden.contains(&m.bundle_id);
+            let rule = rules.matching_rule(&m.path).cloned();
+            (by_bundle_id || rule.is_some()).then_some(HiddenApp {
+                bundle_id: m.bundle_id,
+                name: m.name,
+                path: m.path,
+                by_bundle_id,
+                rule,
+            })
+        })
+        .collect();
+
+    HiddenApps {
+        apps,
+        rules: rules.patterns(),
+    }
+}
+
+fn emit_hidden_apps(app: &tauri::AppHandle) {
+    let _ = app.emit("hidden-apps-changed", hidden_apps());
+}
+
+#[tauri::command]
+fn list_hidden_apps() -> Result<HiddenApps, String> {
+    Ok(hidden_apps())
+}
+
+/// Hide an app from the grid in the current profile (it keeps its place in the layout)
+#[tauri::command]
+fn hide_app(app: tauri::AppHandle, bundle_id: String) -> Result<(), String> {
+    update_layout(&app, |layout| {
+        layout.hidden.insert(bundle_id);
+        Ok(())
+    })?;
+    emit_hidden_apps(&app);
+    Ok(())
+}
+
+/// Show an app hidden by bundle ID again (apps hidden by a path rule stay hidden)
+#[tauri::command]
+fn unhide_app(app: tauri::AppHandle, bundle_id: String) -> Result<(), String> {
+    update_layout(&app, |layout| {
+        layout.hidden.remove(&bundle_id);
+        Ok(())
+    })?;
+    emit_hidden_apps(&app);
+    Ok(())
+}
+
+/// Hide every app whose bundle path matches a pattern
+#[tauri::command]
+fn add_hidden_path_rule(app: tauri::AppHandle, pattern: String) -> Result<(), String> {
+    {
+        let mut rules = hidden_rules().lock().unwrap();
+        if rules.add(&pattern)? {
+            rules.save()?;
+        }
+    }
+    emit_hidden_apps(&app);
+    Ok(())
+}
+
+#[tauri::command]
+fn remove_hidden_path_rule(app: tauri::AppHandle, pattern: String) -> Result<(), String> {
+    {
+        let mut rules = hidden_rules().lock().unwrap();
+        if !rules.remove(&pattern) {
+            return Err(format!("No hidden path rule: {}", pattern));
+        }
+        rules.save()?;
+    }
+    emit_hidden_apps(&app);
+    Ok(())
+}
+
+/// Write pages, folders, tags, custom icons, hidden apps and favorites to a portable JSON file
+#[tauri::command]
+fn export_layout(path: String) -> Result<(), String> {
+    let app_names: BTreeMap<String, String> = scan_all_app_metadata()
+        .into_iter()
+        .map(|a| (a.bundle_id, a.name))
+        .collect();
+
+    let (custom_tags, tags) = {
+        let store = tag_store().lock().unwrap();
+        let tags: BTreeMap<String, Vec<String>> = store
+            .assignments()
+            .into_iter()
+            .filter(|(bundle_id, tags)| !tags.is_empty() && app_names.contains_key(bundle_id))
+            .collect();
+        (store.custom_tags().to_vec(), tags)
+    };
+
+    let layout = layout_store().lock().unwrap().clone();
+    let export = layout_transfer::export_layout(&layout, &custom_tags, tags, app_names);
+    layout_transfer::write_export(Path::new(&path), &export)
+}
+
+/// Apply a layout file written by export_layout. Reports the referenced apps
+/// that aren't installed on this machine.
+#[tauri::command]
+fn import_layout(
+    app: tauri::AppHandle,
+    path: String,
+    strategy: ImportStrategy,
+) -> Result<LayoutImportReport, String> {
+    let mut export = layout_transfer::read_export(Path::new(&path))?;
+    custom_icons::store_data_urls(&mut export.custom_icons);
+
+    let installed = installed_bundle_ids();
+
+    snapshot_layout(SnapshotReason::BeforeImport);
+    let report = update_layout(&app, |layout| {
+        Ok(layout_transfer::apply_import(layout, &export, &installed, strategy))
+    })?;
+
+    if strategy != ImportStrategy::FoldersOnly {
+        let mut store = tag_store().lock().unwrap();
+        let keep_local = strategy == ImportStrategy::Merge;
+        store.import_tags(&export.custom_tags, &export.tags, keep_local);
+        store.save()?;
+        for bundle_id in export.tags.keys() {
+            write_back_finder_tags(&mut store, bundle_id, &[]);
+        }
+        store.save()?;
+    }
+
+    Ok(report)
+}
+
+#[tauri::command]
+fn get_profiles() -> Result<ProfileStore, String> {
+    Ok(profile_store().lock().unwrap().clone())
+}
+
+/// Create a profile with all apps in alphabetical order on the grid from the settings
+#[tauri::command]
+fn create_profile(name: String) -> Result<Profile, String> {
+    let installed = installed_bundle_ids();
+    let (rows, cols) = {
+        let settings = settings().lock().unwrap();
+        (settings.rows, settings.cols)
+    };
+
+    let mut store = profile_store().lock().unwrap();
+    let profile = store.create(&name, rows, cols)?;
+
+    let mut layout = Layout {
+        page_size: profile.page_size(),
+        ..Layout::default()
+    };
+    layout.sync_apps(&installed);
+    layout.save(&profile.id)?;
+    store.save()?;
+
+    Ok(profile)
+}
+
+/// Create a profile starting from a copy of another one's layout and grid
+#[tauri::command]
+fn clone_profile(source_id: String, name: String) -> Result<Profile, String> {
+    let source_layout = if source_id == active_profile_id() {
+        layout_store().lock().unwrap().clone()
+    } else {
+        Layout::load(&source_id)
+    };
+
+    let mut store = profile_store().lock().unwrap();
+    let source = store
+        .get(&source_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown profile: {}", source_id))?;
+    let profile = store.create(&name, source.rows, source.cols)?;
+
+    source_layout.save(&profile.id)?;
+    store.save()?;
+
+    Ok(profile)
+}
+
+#[tauri::command]
+fn rename_profile(profile_id: String, name: String) -> Result<(), String> {
+    let mut store = profile_store().lock().unwrap();
+    store.rename(&profile_id, &name)?;
+    store.save()
+}
+
+#[tauri::command]
+fn delete_profile(app: tauri::AppHandle, profile_id: String) -> Result<(), String> {
+    {
+        let mut store = profile_store().lock().unwrap();
+        store.delete(&profile_id)?;
+        store.save()?;
+    }
+    Layout::delete_file(&profile_id)?;
+    register_profile_shortcuts(&app);
+    Ok(())
+}
+
+#[tauri::command]
+fn switch_profile(app: tauri::AppHandle, profile_id: String) -> Result<Profile, String> {
+    switch_to_profile(&app, &profile_id)
+}
+
+/// Make another profile active. Its layout replaces the current one and the undo history starts over.
+fn switch_to_profile(app: &tauri::AppHandle, profile_id: &str) -> Result<Profile, String> {
+    if active_profile_id() == profile_id {
+        return Ok(profile_store().lock().unwrap().active().clone());
+    }
+
+    let installed = installed_bundle_ids();
+
+    let mut layout = layout_store().lock().unwrap();
+    let mut store = profile_store().lock().unwrap();
+    let profile = store
+        .get(profile_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown profile: {}", profile_id))?;
+
+    let mut next = Layout::load(profile_id);
+    next.sync_apps(&installed);
+    next.save(profile_id)?;
+    store.set_active(profile_id)?;
+    store.save()?;
+    *layout = next;
+    drop(store);
+
+    let mut history = undo_history().lock().unwrap();
+    *history = UndoHistory::default();
+    if let Err(e) = history.save() {
+        eprintln!("[LayoutHistory] {}", e);
+    }
+
+    publish_layout(&layout);
+
+    let _ = app.emit("layout-changed", layout.clone());
+    let _ = app.emit("profile-changed", profile.clone());
+    show_profile_grid(app, &profile);
+    Ok(profile)
+}
+
+/// Copy the active profile's grid into the settings the frontend renders from and emit
+/// "settings-changed" (takes the settings lock, so not while it is held)
+fn show_profile_grid(app: &tauri::AppHandle, profile: &Profile) {
+    let mut current = settings().lock().unwrap();
+    if (current.rows, current.cols) == (profile.rows, profile.cols) {
+        return;
+    }
+    current.rows = profile.rows;
+    current.cols = profile.cols;
+    if let Err(e) = current.save() {
+        eprintln!("[Settings] {}", e);
+    }
+    let _ = app.emit("settings-changed", current.clone());
+}
+
+/// Change a profile's grid; its pages reflow to the new size
+#[tauri::command]
+fn set_profile_grid(
+    app: tauri::AppHandle,
+    profile_id: String,
+    rows: usize,
+    cols: usize,
+) -> Result<(), String> {
+    let mut layout = layout_store().lock().unwrap();
+    resize_profile(&app, &mut layout, &profile_id, rows, cols)?;
+
+    if active_profile_id() == profile_id {
+        let profile = profile_store().lock().unwrap().active().clone();
+        show_profile_grid(&app, &profile);
+    }
+    Ok(())
+}
+
+/// `set_profile_grid` with the layout store already locked (`layout` is the active layout)
+fn resize_profile(
+    app: &tauri::AppHandle,
+    layout: &mut Layout,
+    profile_id: &str,
+    rows: usize,
+    cols: usize,
+) -> Result<(), String> {
+    let profile = {
+        let mut store = profile_store().lock().unwrap();
+        store.set_grid(profile_id, rows, cols)?;
+        store.save()?;
+        store
+            .get(profile_id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown profile: {}", profile_id))?
+    };
+
+    if active_profile_id() == profile_id {
+        update_locked_layout(app, layout, |layout| layout.set_page_size(profile.page_size()))?;
+        let _ = app.emit("profile-changed", profile);
+        Ok(())
+    } else {
+        let mut layout = Layout::load(profile_id);
+        layout.set_page_size(profile.page_size())?;
+        layout.save(profile_id)
+    }
+}
+
+/// Bind (or with None, unbind) a global shortcut that switches to the profile
+#[tauri::command]
+fn set_profile_shortcut(
+    app: tauri::AppHandle,
+    profile_id: String,
+    shortcut: Option<String>,
+) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::Shortcut;
+
+    let shortcut = shortcut
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if let Some(ref shortcut) = shortcut {
+        shortcut
+            .parse::<Shortcut>()
+            .map_err(|e| format!("Invalid shortcut format: {}", e))?;
+    }
+
+    {
+        let mut store = profile_store().lock().unwrap();
+        store.set_shortcut(&profile_id, shortcut)?;
+        store.save()?;
+    }
+
+    // Only this profile's shortcut is the caller's problem
+    match register_profile_shortcuts(&app)
+        .into_iter()
+        .find(|(id, _)| *id == profile_id)
+    {
+        Some((_, error)) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Register the global shortcuts that switch profiles, replacing the previous ones.
+/// A shortcut that fails (invalid, or taken by another app) is skipped; returns the
+/// failures as (profile ID, error).
+fn register_profile_shortcuts(app: &tauri::AppHandle) -> Vec<(String, String)> {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+    let mut registered = PROFILE_SHORTCUTS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap();
+    for shortcut in registered.drain(..) {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+
+    let mut failures = Vec::new();
+    let profiles = profile_store().lock().unwrap().profiles().to_vec();
+    for profile in profiles {
+        let Some(shortcut) = profile.shortcut else {
+            continue;
+        };
+        let shortcut_key: Shortcut = match shortcut.parse() {
+            Ok(key) => key,
+            Err(e) => {
+                let error = format!("Invalid shortcut format: {}", e);
+                eprintln!("[Profiles] {}: {}", profile.id, error);
+                failures.push((profile.id, error));
+                continue;
+            }
+        };
+
+        let profile_id = profile.id.clone();
+        let result = app
+            .global_shortcut()
+            .on_shortcut(shortcut_key, move |app, _shortcut, event| {
+                if event.state != ShortcutState::Pressed {
+                    return;
+                }
+                // Switching rescans the apps, so keep it off the event loop
+                let app = app.clone();
+                let profile_id = profile_id.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = switch_to_profile(&app, &profile_id) {
+                        eprintln!("[Profiles] Failed to switch profile: {}", e);
+                    }
+                });
+            });
+        match result {
+            Ok(()) => registered.push(shortcut_key),
+            Err(e) => {
+                let error = format!("Failed to register shortcut {}: {}", shortcut, e);
+                eprintln!("[Profiles] {}: {}", profile.id, error);
+                failures.push((profile.id, error));
+            }
+        }
+    }
+
+    failures
+}
+
+#[tauri::command]
+fn get_settings() -> Result<Settings, String> {
+    Ok(settings().lock().unwrap().clone())
+}
+
+/// Validate, save and apply new settings, then emit "settings-changed". The settings stay
+/// locked throughout so updates can't interleave; if applying fails they are rolled back.
+#[tauri::command]
+fn update_settings(app: tauri::AppHandle, settings: Settings) -> Result<Settings, String> {
+    let new_settings = Settings {
+        version: settings::SETTINGS_VERSION,
+        ..settings
+    };
+    new_settings.validate()?;
+
+    // The layout store is locked before the settings, like everywhere else (a grid change
+    // resizes the layout)
+    let mut layout = layout_store().lock().unwrap();
+    let mut current = self::settings().lock().unwrap();
+    let previous = current.clone();
+    new_settings.save()?;
+    *current = new_settings.clone();
+
+    if let Err(e) = apply_settings(&app, &mut layout, &previous, &new_settings) {
+        *current = previous.clone();
+        if let Err(e) = previous.save() {
+            eprintln!("[Settings] {}", e);
+        }
+        if let Err(e) = apply_settings(&app, &mut layout, &new_settings, &previous) {
+            eprintln!("[Settings] Failed to restore previous settings: {}", e);
+        }
+        return Err(e);
+    }
+
+    let _ = app.emit("settings-changed", new_settings.clone());
+    Ok(new_settings)
+}
+
+/// Apply what differs between two settings (hot corner, shortcut, grid of the active profile)
+fn apply_settings(
+    app: &tauri::AppHandle,
+    layout: &mut Layout,
+    from: &Settings,
+    to: &Settings,
+) -> Result<(), String> {
+    if to.hot_corner != from.hot_corner {
+        apply_hot_corner(to);
+    }
+    if to.global_shortcut != from.global_shortcut {
+        apply_global_shortcut(app, &to.global_shortcut)?;
+    }
+    if (to.rows, to.cols) != (from.rows, from.cols) {
+        resize_profile(app, layout, &active_profile_id(), to.rows, to.cols)?;
+    }
+    Ok(())
+}
+
+/// Take over settings saved by an older version (e.g. the frontend's localStorage blob)
+#[tauri::command]
+fn import_legacy_settings(
+    app: tauri::AppHandle,
+    settings: serde_json::Value,
+) -> Result<Settings, String> {
+    update_settings(app, Settings::from_stored(settings)?)
+}
+
+fn apply_hot_corner(settings: &Settings) {
+    let Some(monitor) = HOT_CORNER_MONITOR.get() else {
+        return;
+    };
+    let config = settings.hot_corner.config();
+    if config.enabled {
+        monitor.update_config(config);
+        // Start the listener thread if not already started
+        // (idempotent - will only start once)
+        monitor.start();
+    } else {
+        monitor.set_enabled(false);
+    }
+}
+
 #[tauri::command]
 fn enable_hot_corner(
+    app: tauri::AppHandle,
     corner: String,
     threshold: f64,
     debounce_ms: u64,
@@ -1004,29 +3152,21 @@ fn enable_hot_corner(
         _ => return Err("Invalid corner".to_string()),
     };
 
-    let config = HotCornerConfig {
+    let mut new_settings = settings().lock().unwrap().clone();
+    new_settings.hot_corner = settings::HotCornerSettings {
         enabled: corner_enum != Corner::Disabled,
         corner: corner_enum,
-        trigger_threshold: threshold,
+        threshold,
         debounce_ms,
     };
-
-    if let Some(monitor) = HOT_CORNER_MONITOR.get() {
-        monitor.update_config(config);
-        // Start the listener thread if not already started
-        // (idempotent - will only start once)
-        monitor.start();
-    }
-
-    Ok(())
+    update_settings(app, new_settings).map(|_| ())
 }
 
 #[tauri::command]
-fn disable_hot_corner() -> Result<(), String> {
-    if let Some(monitor) = HOT_CORNER_MONITOR.get() {
-        monitor.set_enabled(false);
-    }
-    Ok(())
+fn disable_hot_corner(app: tauri::AppHandle) -> Result<(), String> {
+    let mut new_settings = settings().lock().unwrap().clone();
+    new_settings.hot_corner.enabled = false;
+    update_settings(app, new_settings).map(|_| ())
 }
 
 #[tauri::command]
@@ -1122,6 +3262,13 @@ async fn position_on_cursor_monitor(app: tauri::AppHandle) -> Result<(), String>
 
 #[tauri::command]
 fn register_global_shortcut(app: tauri::AppHandle, shortcut: String) -> Result<(), String> {
+    let mut new_settings = settings().lock().unwrap().clone();
+    new_settings.global_shortcut = shortcut;
+    update_settings(app, new_settings).map(|_| ())
+}
+
+/// Register the shortcut that shows the window (along with the profile shortcuts)
+fn apply_global_shortcut(app: &tauri::AppHandle, shortcut: &str) -> Result<(), String> {
     use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
 
     // Unregister any existing shortcuts first
@@ -1140,6 +3287,8 @@ fn register_global_shortcut(app: tauri::AppHandle, shortcut: String) -> Result<(
         })
         .map_err(|e| format!("Failed to register shortcut: {}", e))?;
 
+    // unregister_all above also dropped the profile shortcuts; their failures are logged
+    register_profile_shortcuts(app);
     Ok(())
 }
 
@@ -1195,7 +3344,79 @@ pub fn run() {
             enable_hot_corner,
             disable_hot_corner,
             register_global_shortcut,
-            get_system_accent_color
+            get_settings,
+            update_settings,
+            import_legacy_settings,
+            get_system_accent_color,
+            get_custom_tags,
+            create_tag,
+            rename_tag,
+            delete_tag,
+            reset_tags,
+            assign_tag,
+            unassign_tag,
+            get_tag_assignments,
+            get_apps_with_tag,
+            get_tag_counts,
+            import_legacy_tags,
+            get_finder_tag_sync,
+            set_finder_tag_sync,
+            get_layout,
+            move_layout_item,
+            move_app_to_folder,
+            create_folder,
+            rename_folder,
+            delete_folder,
+            merge_layout_items,
+            set_layout_page_size,
+            import_legacy_layout,
+            import_launchpad_database,
+            seed_layout_from_dock,
+            set_custom_icon,
+            export_layout,
+            import_layout,
+            undo_layout,
+            redo_layout,
+            get_undo_state,
+            create_layout_snapshot,
+            list_layout_snapshots,
+            diff_layout_snapshot,
+            restore_layout_snapshot,
+            delete_layout_snapshot,
+            reset_layout,
+            get_profiles,
+            create_profile,
+            clone_profile,
+            rename_profile,
+            delete_profile,
+            switch_profile,
+            set_profile_grid,
+            set_profile_shortcut,
+            get_layout_sync,
+            set_layout_sync_directory,
+            set_sync_device_name,
+            sync_layout_now,
+            create_smart_folder,
+            set_smart_folder_rules,
+            refresh_smart_folders_now,
+            auto_organize,
+            sort_layout,
+            list_hidden_apps,
+            hide_app,
+            unhide_app,
+            add_hidden_path_rule,
+            remove_hidden_path_rule,
+            get_favorites,
+            pin_favorite,
+            unpin_favorite,
+            reorder_favorites,
+            get_display_names,
+            set_display_name,
+            get_recent_apps,
+            launch_app_with_options,
+            get_launch_presets,
+            save_launch_preset,
+            delete_launch_preset
         ])
         .setup(|app| {
             let window = app.get_webview_window("main").unwrap();
@@ -1217,29 +3438,41 @@ pub fn run() {
                     let _ = app_handle.emit("hot-corner-triggered", corner);
                 },
             );
-            // DON'T start the monitor here - it will be started when user enables it in settings
+            // The listener only starts once the hot corner is enabled
             let _ = HOT_CORNER_MONITOR.set(monitor);
+            let startup_settings = settings().lock().unwrap().clone();
+            apply_hot_corner(&startup_settings);
 
-            // Initialize app watcher to detect new/removed apps
+            // Initialize app watcher to detect new/removed apps; changes are diffed
+            // against the inventory, so it's scanned (off the startup thread) before
+            // the watcher can report any
             let app_handle_watcher = app.handle().clone();
-            let mut watcher = AppWatcher::new(move || {
-                println!("[AppWatcher] Apps changed, emitting event");
-                let _ = app_handle_watcher.emit("apps-changed", ());
-            });
-            watcher.start();
-            let _ = APP_WATCHER.set(Mutex::new(watcher));
-
-            // Register default global shortcut (F4)
-            // Frontend will override this with user's saved preference if different
-            use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
-            let default_shortcut: Shortcut = "F4".parse().unwrap();
-            let _ = app
-                .global_shortcut()
-                .on_shortcut(default_shortcut, move |app_h, _shortcut, _event| {
-                    if let Some(window) = app_h.get_webview_window("main") {
-                        let _ = window.emit("global-shortcut-triggered", ());
-                    }
+            std::thread::spawn(move || {
+                init_app_inventory();
+                // Path rules can only hide apps once their paths are known
+                let layout = layout_store().lock().unwrap().clone();
+                let _ = app_handle_watcher.emit("layout-changed", layout);
+                let mut watcher = AppWatcher::new(move |paths| {
+                    refresh_changed_apps(&app_handle_watcher, &paths);
                 });
+                watcher.start();
+                let _ = APP_WATCHER.set(Mutex::new(watcher));
+            });
+
+            if let Err(e) = restart_sync_watcher(app.handle()) {
+                eprintln!("[LayoutSync] {}", e);
+            }
+
+            // Snapshot the layout in the background (skipped while it doesn't change)
+            std::thread::spawn(|| loop {
+                std::thread::sleep(layout_history::SCHEDULED_SNAPSHOT_INTERVAL);
+                snapshot_layout(SnapshotReason::Scheduled);
+            });
+
+            // Register the saved global shortcut (and the profile shortcuts)
+            if let Err(e) = apply_global_shortcut(app.handle(), &startup_settings.global_shortcut) {
+                eprintln!("[Settings] {}", e);
+            }
 
             Ok(())
         })
@@ -0,0 +1,99 @@
+use crate::layout::Layout;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Where apps reported as newly installed are put
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlacementPolicy {
+    /// At the end of the last page
+    #[default]
+    AppendToLastPage,
+    /// Into the folder whose apps mostly share the app's tag, else the last page
+    TagFolder,
+    /// Into the new apps folder (named in the settings), created on first use
+    NewAppsFolder,
+}
+
+/// The tag at least half of a folder's apps have (the most common one if several do)
+fn dominant_tag<F>(apps: &[String], tags_for: &F) -> Option<(String, usize)>
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for bundle_id in apps {
+        for tag in tags_for(bundle_id) {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| count * 2 >= apps.len())
+        .max_by(|(a_tag, a), (b_tag, b)| a.cmp(b).then_with(|| b_tag.cmp(a_tag)))
+}
+
+/// Move apps that were just appended to the layout where the policy wants them.
+/// `new_apps_folder` is the (localized) name of the folder the new-apps-folder policy uses.
+pub fn place_new_apps<F>(
+    layout: &mut Layout,
+    added: &[String],
+    policy: PlacementPolicy,
+    new_apps_folder: &str,
+    tags_for: F,
+) -> Result<(), String>
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    if added.is_empty() {
+        return Ok(());
+    }
+
+    match policy {
+        PlacementPolicy::AppendToLastPage => Ok(()),
+        PlacementPolicy::TagFolder => {
+            // Folder tags are worked out before any app moves, so placement doesn't depend on order
+            let folder_tags: Vec<(String, String, f64)> = layout
+                .folders
+                .iter()
+                .filter_map(|folder| {
+                    let (tag, count) = dominant_tag(&folder.apps, &tags_for)?;
+                    let share = count as f64 / folder.apps.len() as f64;
+                    Some((folder.id.clone(), tag, share))
+                })
+                .collect();
+
+            for bundle_id in added {
+                let tags = tags_for(bundle_id);
+                let best = folder_tags
+                    .iter()
+                    .filter(|(_, tag, _)| tags.contains(tag))
+                    .fold(
+                        None::<&(String, String, f64)>,
+                        |best, candidate| match best {
+                            Some(b) if b.2 >= candidate.2 => Some(b),
+                            _ => Some(candidate),
+                        },
+                    );
+                if let Some((folder_id, _, _)) = best {
+                    layout.move_to_folder(bundle_id, folder_id, None)?;
+                }
+            }
+            Ok(())
+        }
+        PlacementPolicy::NewAppsFolder => {
+            let existing = layout
+                .folders
+                .iter()
+                .find(|f| f.name.to_lowercase() == new_apps_folder.to_lowercase())
+                .map(|f| f.id.clone());
+
+            match existing {
+                Some(folder_id) => added
+                    .iter()
+                    .try_for_each(|id| layout.move_to_folder(id, &folder_id, None)),
+                None => layout.create_folder(new_apps_folder, added).map(|_| ()),
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+
+const PROFILES_FILE: &str = "profiles.json";
+
+/// The profile every install starts with; it can't be deleted
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+const DEFAULT_PROFILE_NAME: &str = "Default";
+
+/// Default grid, matching the frontend defaults (7 rows x 10 columns)
+pub const DEFAULT_GRID_ROWS: usize = 7;
+pub const DEFAULT_GRID_COLS: usize = 10;
+
+/// A named layout profile. Pages, folders and hidden apps live in the profile's layout file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub rows: usize,
+    pub cols: usize,
+    /// Global shortcut that switches to this profile
+    #[serde(default)]
+    pub shortcut: Option<String>,
+}
+
+impl Profile {
+    pub fn page_size(&self) -> usize {
+        self.rows * self.cols
+    }
+}
+
+/// All profiles and which one is active
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileStore {
+    active: String,
+    profiles: Vec<Profile>,
+}
+
+impl Default for ProfileStore {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE_ID.to_string(),
+            profiles: vec![Profile {
+                id: DEFAULT_PROFILE_ID.to_string(),
+                name: DEFAULT_PROFILE_NAME.to_string(),
+                rows: DEFAULT_GRID_ROWS,
+                cols: DEFAULT_GRID_COLS,
+                shortcut: None,
+            }],
+        }
+    }
+}
+
+/// Profile ID from its name ("Work Mac" -> "work-mac"), also used in file names
+fn profile_id_from_name(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-')
+        .collect();
+
+    if slug.is_empty() {
+        "profile".to_string()
+    } else {
+        slug
+    }
+}
+
+fn validate_grid(rows: usize, cols: usize) -> Result<(), String> {
+    if rows == 0 || cols == 0 {
+        return Err("Grid needs at least one row and one column".to_string());
+    }
+    Ok(())
+}
+
+impl ProfileStore {
+    /// Load the profiles from the app data dir (just the default profile if missing)
+    pub fn load() -> Self {
+        let mut store: Self = storage::load_json(PROFILES_FILE).unwrap_or_default();
+
+        // The active profile may have been removed by hand
+        if store.get(&store.active).is_none() {
+            store.active = DEFAULT_PROFILE_ID.to_string();
+        }
+        if store.get(DEFAULT_PROFILE_ID).is_none() {
+            store.profiles.insert(0, Self::default().profiles.remove(0));
+        }
+        store
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(PROFILES_FILE, self)
+    }
+
+    pub fn active_id(&self) -> &str {
+        &self.active
+    }
+
+    pub fn active(&self) -> &Profile {
+        self.get(&self.active).unwrap_or(&self.profiles[0])
+    }
+
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    pub fn get(&self, profile_id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.id == profile_id)
+    }
+
+    fn get_mut(&mut self, profile_id: &str) -> Result<&mut Profile, String> {
+        self.profiles
+            .iter_mut()
+            .find(|p| p.id == profile_id)
+            .ok_or_else(|| format!("Unknown profile: {}", profile_id))
+    }
+
+    fn check_name(&self, name: &str, except_id: Option<&str>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Profile name cannot be empty".to_string());
+        }
+
+        let taken = self.profiles.iter().any(|p| {
+            Some(p.id.as_str()) != except_id && p.name.to_lowercase() == name.to_lowercase()
+        });
+        if taken {
+            return Err(format!("A profile named \"{}\" already exists", name));
+        }
+
+        Ok(name.to_string())
+    }
+
+    /// Add a profile with the given grid; its layout file is created by the caller
+    pub fn create(&mut self, name: &str, rows: usize, cols: usize) -> Result<Profile, String> {
+        let name = self.check_name(name, None)?;
+        validate_grid(rows, cols)?;
+
+        let base_id = profile_id_from_name(&name);
+        let mut id = base_id.clone();
+        let mut suffix = 2;
+        while self.get(&id).is_some() {
+            id = format!("{}-{}", base_id, suffix);
+            suffix += 1;
+        }
+
+        let profile = Profile {
+            id,
+            name,
+            rows,
+            cols,
+            shortcut: None,
+        };
+        self.profiles.push(profile.clone());
+        Ok(profile)
+    }
+
+    pub fn rename(&mut self, profile_id: &str, name: &str) -> Result<(), String> {
+        let name = self.check_name(name, Some(profile_id))?;
+        self.get_mut(profile_id)?.name = name;
+        Ok(())
+    }
+
+    pub fn set_grid(&mut self, profile_id: &str, rows: usize, cols: usize) -> Result<(), String> {
+        validate_grid(rows, cols)?;
+        let profile = self.get_mut(profile_id)?;
+        profile.rows = rows;
+        profile.cols = cols;
+        Ok(())
+    }
+
+    pub fn set_shortcut(&mut self, profile_id: &str, shortcut: Option<String>) -> Result<(), String> {
+        self.get_mut(profile_id)?.shortcut = shortcut;
+        Ok(())
+    }
+
+    pub fn set_active(&mut self, profile_id: &str) -> Result<(), String> {
+        self.get_mut(profile_id)?;
+        self.active = profile_id.to_string();
+        Ok(())
+    }
+
+    /// Remove a profile (not the active or the default one); its layout file is removed by the caller
+    pub fn delete(&mut self, profile_id: &str) -> Result<(), String> {
+        if profile_id == DEFAULT_PROFILE_ID {
+            return Err("The default profile cannot be deleted".to_string());
+        }
+        if profile_id == self.active {
+            return Err("Switch to another profile before deleting this one".to_string());
+        }
+
+        self.get_mut(profile_id)?;
+        self.profiles.retain(|p| p.id != profile_id);
+        Ok(())
+    }
+}
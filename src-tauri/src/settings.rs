@@ -0,0 +1,415 @@
+use crate::collation::{DEFAULT_LANGUAGE, SUPPORTED_LANGUAGES};
+use crate::hot_corners::{Corner, HotCornerConfig};
+use crate::placement::PlacementPolicy;
+use crate::profiles::{DEFAULT_GRID_COLS, DEFAULT_GRID_ROWS};
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::ops::RangeInclusive;
+use tauri_plugin_global_shortcut::Shortcut;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Current settings schema version; older files are migrated on load
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Allowed values, matching the sliders in the settings screens
+pub const ROWS_RANGE: RangeInclusive<usize> = 3..=10;
+pub const COLS_RANGE: RangeInclusive<usize> = 5..=25;
+
+/// Distance from the corner (in points) that triggers the hot corner
+pub const HOT_CORNER_THRESHOLD_RANGE: RangeInclusive<f64> = 5.0..=50.0;
+
+pub const HOT_CORNER_DEBOUNCE_RANGE: RangeInclusive<u64> = 100..=10_000;
+
+/// Days an app keeps its "New" or "Updated" badge if it isn't launched
+pub const NEW_BADGE_DAYS_RANGE: RangeInclusive<u64> = 1..=90;
+
+const DEFAULT_SHORTCUT: &str = "F4";
+
+const DEFAULT_NEW_APPS_FOLDER_NAME: &str = "New";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotCornerSettings {
+    pub enabled: bool,
+    pub corner: Corner,
+    pub threshold: f64,
+    pub debounce_ms: u64,
+}
+
+impl Default for HotCornerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            corner: Corner::TopLeft,
+            threshold: 10.0,
+            debounce_ms: 5000,
+        }
+    }
+}
+
+impl HotCornerSettings {
+    pub fn config(&self) -> HotCornerConfig {
+        HotCornerConfig {
+            enabled: self.enabled && self.corner != Corner::Disabled,
+            corner: self.corner,
+            trigger_threshold: self.threshold,
+            debounce_ms: self.debounce_ms,
+        }
+    }
+}
+
+/// Tag bar settings (custom tags themselves live in the tag store)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagSettings {
+    pub show_tag_bar: bool,
+    pub auto_tag_new_apps: bool,
+}
+
+impl Default for TagSettings {
+    fn default() -> Self {
+        Self {
+            show_tag_bar: true,
+            auto_tag_new_apps: true,
+        }
+    }
+}
+
+/// Where the favorites strip is shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FavoritesPosition {
+    /// In a row of its own below the grid
+    #[default]
+    Row,
+    /// In the first slots of every page
+    EveryPage,
+}
+
+/// App settings, owned by the backend and applied by it at startup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    /// Grid for new profiles; changing it also resizes the active profile
+    pub rows: usize,
+    pub cols: usize,
+    pub full_width: bool,
+    pub hot_corner: HotCornerSettings,
+    pub global_shortcut: String,
+    pub tags: TagSettings,
+    /// Where newly installed apps are put in the layout
+    pub new_app_placement: PlacementPolicy,
+    /// Name of the folder new apps go into, translated by the frontend
+    pub new_apps_folder_name: String,
+    /// UI language; app names are sorted by its collation rules
+    pub language: String,
+    /// Where the favorites strip is shown
+    pub favorites_position: FavoritesPosition,
+    /// Days an app keeps its "New" or "Updated" badge if it isn't launched
+    pub new_badge_days: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            rows: DEFAULT_GRID_ROWS,
+            cols: DEFAULT_GRID_COLS,
+            full_width: false,
+            hot_corner: HotCornerSettings::default(),
+            global_shortcut: DEFAULT_SHORTCUT.to_string(),
+            tags: TagSettings::default(),
+            new_app_placement: PlacementPolicy::default(),
+            new_apps_folder_name: DEFAULT_NEW_APPS_FOLDER_NAME.to_string(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            favorites_position: FavoritesPosition::default(),
+            new_badge_days: 7,
+        }
+    }
+}
+
+fn valid_shortcut(shortcut: &str) -> Result<(), String> {
+    shortcut
+        .parse::<Shortcut>()
+        .map(|_| ())
+        .map_err(|e| format!("Invalid shortcut \"{}\": {}", shortcut, e))
+}
+
+fn valid_language(language: &str) -> Result<(), String> {
+    if SUPPORTED_LANGUAGES.contains(&language) {
+        Ok(())
+    } else {
+        Err(format!("Unsupported language \"{}\"", language))
+    }
+}
+
+/// Migrations indexed by the version they upgrade from
+const MIGRATIONS: &[fn(Value) -> Value] = &[migrate_frontend_settings];
+
+/// Version 0 is the `launchpad-grid-settings` blob the frontend kept in localStorage (camelCase, flat),
+/// optionally with `language` from `launchpad-language`
+fn migrate_frontend_settings(old: Value) -> Value {
+    fn copy(from: &Value, from_key: &str, to: &mut Map<String, Value>, to_key: &str) {
+        if let Some(value) = from.get(from_key).filter(|v| !v.is_null()) {
+            to.insert(to_key.to_string(), value.clone());
+        }
+    }
+
+    let mut hot_corner = Map::new();
+    copy(&old, "hotCornerEnabled", &mut hot_corner, "enabled");
+    copy(&old, "hotCorner", &mut hot_corner, "corner");
+    copy(&old, "hotCornerThreshold", &mut hot_corner, "threshold");
+    copy(&old, "hotCornerDebounce", &mut hot_corner, "debounce_ms");
+
+    let mut tags = Map::new();
+    if let Some(tag_settings) = old.get("tagSettings") {
+        copy(tag_settings, "showTagBar", &mut tags, "show_tag_bar");
+        copy(
+            tag_settings,
+            "autoTagNewApps",
+            &mut tags,
+            "auto_tag_new_apps",
+        );
+    }
+
+    let mut settings = Map::new();
+    settings.insert("version".to_string(), Value::from(1));
+    copy(&old, "rows", &mut settings, "rows");
+    copy(&old, "cols", &mut settings, "cols");
+    copy(&old, "fullWidth", &mut settings, "full_width");
+    copy(&old, "globalShortcut", &mut settings, "global_shortcut");
+    copy(&old, "language", &mut settings, "language");
+    settings.insert("hot_corner".to_string(), Value::Object(hot_corner));
+    settings.insert("tags".to_string(), Value::Object(tags));
+    Value::Object(settings)
+}
+
+/// Bring stored settings up to the current version. Returns whether anything was migrated.
+fn migrate(mut value: Value) -> Result<(Value, bool), String> {
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version > SETTINGS_VERSION {
+        return Err(format!(
+            "Settings version {} is newer than supported version {}",
+            version, SETTINGS_VERSION
+        ));
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        value = migration(value);
+    }
+    Ok((value, version < SETTINGS_VERSION))
+}
+
+impl Settings {
+    /// Load the settings, migrating older versions and resetting invalid values to defaults
+    pub fn load() -> Self {
+        let Some(stored) = storage::load_json::<Value>(SETTINGS_FILE) else {
+            return Self::default();
+        };
+
+        let loaded = migrate(stored).and_then(|(value, migrated)| {
+            let settings: Self = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            Ok((settings, migrated))
+        });
+        match loaded {
+            Ok((settings, migrated)) => {
+                let sanitized = settings.clone().sanitized();
+                if migrated || sanitized != settings {
+                    if let Err(e) = sanitized.save() {
+                        eprintln!("[Settings] {}", e);
+                    }
+                }
+                sanitized
+            }
+            Err(e) => {
+                eprintln!("[Settings] Failed to load settings, using defaults: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Settings from any earlier version (e.g. the frontend's localStorage blob), validated
+    pub fn from_stored(value: Value) -> Result<Self, String> {
+        let (value, _) = migrate(value)?;
+        let settings: Self =
+            serde_json::from_value(value).map_err(|e| format!("Invalid settings: {}", e))?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(SETTINGS_FILE, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !ROWS_RANGE.contains(&self.rows) {
+            return Err(format!(
+                "Rows must be between {} and {}",
+                ROWS_RANGE.start(),
+                ROWS_RANGE.end()
+            ));
+        }
+        if !COLS_RANGE.contains(&self.cols) {
+            return Err(format!(
+                "Columns must be between {} and {}",
+                COLS_RANGE.start(),
+                COLS_RANGE.end()
+            ));
+        }
+        if !HOT_CORNER_THRESHOLD_RANGE.contains(&self.hot_corner.threshold) {
+            return Err(format!(
+                "Hot corner threshold must be between {} and {}",
+                HOT_CORNER_THRESHOLD_RANGE.start(),
+                HOT_CORNER_THRESHOLD_RANGE.end()
+            ));
+        }
+        if !HOT_CORNER_DEBOUNCE_RANGE.contains(&self.hot_corner.debounce_ms) {
+            return Err(format!(
+                "Hot corner debounce must be between {} and {} ms",
+                HOT_CORNER_DEBOUNCE_RANGE.start(),
+                HOT_CORNER_DEBOUNCE_RANGE.end()
+            ));
+        }
+        if !NEW_BADGE_DAYS_RANGE.contains(&self.new_badge_days) {
+            return Err(format!(
+                "Badge period must be between {} and {} days",
+                NEW_BADGE_DAYS_RANGE.start(),
+                NEW_BADGE_DAYS_RANGE.end()
+            ));
+        }
+        if self.new_apps_folder_name.trim().is_empty() {
+            return Err("New apps folder name cannot be empty".to_string());
+        }
+        valid_shortcut(&self.global_shortcut)?;
+        valid_language(&self.language)
+    }
+
+    /// Replace invalid values with their defaults (for files edited by hand)
+    fn sanitized(mut self) -> Self {
+        let defaults = Self::default();
+        self.version = SETTINGS_VERSION;
+        if !ROWS_RANGE.contains(&self.rows) {
+            self.rows = defaults.rows;
+        }
+        if !COLS_RANGE.contains(&self.cols) {
+            self.cols = defaults.cols;
+        }
+        if !HOT_CORNER_THRESHOLD_RANGE.contains(&self.hot_corner.threshold) {
+            self.hot_corner.threshold = defaults.hot_corner.threshold;
+        }
+        if !HOT_CORNER_DEBOUNCE_RANGE.contains(&self.hot_corner.debounce_ms) {
+            self.hot_corner.debounce_ms = defaults.hot_corner.debounce_ms;
+        }
+        if !NEW_BADGE_DAYS_RANGE.contains(&self.new_badge_days) {
+            self.new_badge_days = defaults.new_badge_days;
+        }
+        if self.new_apps_folder_name.trim().is_empty() {
+            self.new_apps_folder_name = defaults.new_apps_folder_name;
+        }
+        if valid_shortcut(&self.global_shortcut).is_err() {
+            self.global_shortcut = defaults.global_shortcut;
+        }
+        if valid_language(&self.language).is_err() {
+            self.language = defaults.language;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// `launchpad-grid-settings` as the frontend saved it, plus `launchpad-language`
+    fn frontend_blob() -> Value {
+        json!({
+            "rows": 5,
+            "cols": 8,
+            "fullWidth": true,
+            "hotCornerEnabled": true,
+            "hotCorner": "bottom-right",
+            "hotCornerThreshold": 20,
+            "hotCornerDebounce": 1500,
+            "globalShortcut": "CommandOrControl+Shift+L",
+            "tagSettings": {
+                "showTagBar": false,
+                "autoTagNewApps": false,
+                "customTags": [{ "key": "custom-work", "label": "Work" }]
+            },
+            "language": "de"
+        })
+    }
+
+    #[test]
+    fn migrates_frontend_settings() {
+        let settings = Settings::from_stored(frontend_blob()).unwrap();
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!((settings.rows, settings.cols), (5, 8));
+        assert!(settings.full_width);
+        assert_eq!(
+            settings.hot_corner,
+            HotCornerSettings {
+                enabled: true,
+                corner: Corner::BottomRight,
+                threshold: 20.0,
+                debounce_ms: 1500,
+            }
+        );
+        assert_eq!(settings.global_shortcut, "CommandOrControl+Shift+L");
+        assert_eq!(
+            settings.tags,
+            TagSettings {
+                show_tag_bar: false,
+                auto_tag_new_apps: false,
+            }
+        );
+        assert_eq!(settings.language, "de");
+        // Settings the frontend never had keep their defaults
+        let defaults = Settings::default();
+        assert_eq!(settings.new_badge_days, defaults.new_badge_days);
+        assert_eq!(settings.new_apps_folder_name, defaults.new_apps_folder_name);
+    }
+
+    #[test]
+    fn missing_frontend_fields_keep_defaults() {
+        let settings = Settings::from_stored(json!({ "rows": 4, "hotCorner": null })).unwrap();
+
+        let defaults = Settings::default();
+        assert_eq!((settings.rows, settings.cols), (4, defaults.cols));
+        assert_eq!(settings.hot_corner, defaults.hot_corner);
+        assert_eq!(settings.language, defaults.language);
+    }
+
+    #[test]
+    fn rejects_out_of_range_frontend_settings() {
+        let with = |key: &str, value: Value| {
+            let mut blob = frontend_blob();
+            blob[key] = value;
+            Settings::from_stored(blob).unwrap_err()
+        };
+
+        assert_eq!(with("rows", json!(11)), "Rows must be between 3 and 10");
+        assert_eq!(with("cols", json!(4)), "Columns must be between 5 and 25");
+        assert_eq!(
+            with("hotCornerThreshold", json!(60)),
+            "Hot corner threshold must be between 5 and 50"
+        );
+        assert_eq!(
+            with("hotCornerDebounce", json!(50)),
+            "Hot corner debounce must be between 100 and 10000 ms"
+        );
+        assert_eq!(with("language", json!("xx")), "Unsupported language \"xx\"");
+        assert!(with("globalShortcut", json!("Nope+")).starts_with("Invalid shortcut"));
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let newer = json!({ "version": SETTINGS_VERSION + 1 });
+        assert!(Settings::from_stored(newer).is_err());
+    }
+}
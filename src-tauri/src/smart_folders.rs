@@ -0,0 +1,268 @@
+use crate::app_arch::Arch;
+use crate::collation::NameCollator;
+use crate::layout::{Folder, ItemRef, Layout};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// One condition of a smart folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SmartRule {
+    /// Has the tag (detected, Finder or manual)
+    Tag { tag: String },
+    /// Bundle created on disk within the last `days` days
+    InstalledWithin { days: u64 },
+    /// App Store developer or copyright notice contains the text (case-insensitive)
+    DeveloperContains { text: String },
+    /// Built for Intel only (runs under Rosetta on Apple silicon)
+    IntelOnly,
+    /// Last launched from Launchpad more than `days` days ago. Apps never launched from it
+    /// don't match: their last launch is unknown, not long ago.
+    NotLaunchedWithin { days: u64 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleMatch {
+    #[default]
+    All,
+    Any,
+}
+
+/// Rules of a smart folder; its apps are recomputed from them after every scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartRules {
+    #[serde(default)]
+    pub mode: RuleMatch,
+    pub rules: Vec<SmartRule>,
+}
+
+/// What the rules are evaluated against for one installed app
+#[derive(Debug, Clone)]
+pub struct AppFacts {
+    pub bundle_id: String,
+    pub name: String,
+    /// Bundle path
+    pub path: String,
+    pub tags: Vec<String>,
+    pub developer: Option<String>,
+    pub copyright: Option<String>,
+    /// Seconds since the Unix epoch
+    pub installed_at: Option<u64>,
+    pub archs: Vec<Arch>,
+    pub last_launched: Option<u64>,
+}
+
+fn within(timestamp: Option<u64>, days: u64, now: u64) -> bool {
+    timestamp.is_some_and(|t| now.saturating_sub(t) <= days * DAY_SECS)
+}
+
+impl SmartRule {
+    fn matches(&self, app: &AppFacts, now: u64) -> bool {
+        match self {
+            SmartRule::Tag { tag } => app.tags.contains(tag),
+            SmartRule::InstalledWithin { days } => within(app.installed_at, *days, now),
+            SmartRule::DeveloperContains { text } => {
+                let text = text.to_lowercase();
+                [&app.developer, &app.copyright]
+                    .into_iter()
+                    .flatten()
+                    .any(|value| value.to_lowercase().contains(&text))
+            }
+            SmartRule::IntelOnly => {
+                app.archs.contains(&Arch::X86_64) && !app.archs.contains(&Arch::Arm64)
+            }
+            SmartRule::NotLaunchedWithin { days } => {
+                app.last_launched.is_some() && !within(app.last_launched, *days, now)
+            }
+        }
+    }
+}
+
+impl SmartRules {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rules.is_empty() {
+            return Err("A smart folder needs at least one rule".to_string());
+        }
+        for rule in &self.rules {
+            match rule {
+                SmartRule::Tag { tag } if tag.trim().is_empty() => {
+                    return Err("Tag rule needs a tag".to_string())
+                }
+                SmartRule::DeveloperContains { text } if text.trim().is_empty() => {
+                    return Err("Developer rule needs some text".to_string())
+                }
+                SmartRule::InstalledWithin { days } | SmartRule::NotLaunchedWithin { days }
+                    if *days == 0 =>
+                {
+                    return Err("Number of days must be at least 1".to_string())
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, app: &AppFacts, now: u64) -> bool {
+        match self.mode {
+            RuleMatch::All => self.rules.iter().all(|r| r.matches(app, now)),
+            RuleMatch::Any => self.rules.iter().any(|r| r.matches(app, now)),
+        }
+    }
+}
+
+/// Add an (empty) smart folder at the end of the layout; call refresh to fill it
+pub fn create_smart_folder(
+    layout: &mut Layout,
+    name: &str,
+    rules: SmartRules,
+) -> Result<Folder, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Folder name cannot be empty".to_string());
+    }
+    rules.validate()?;
+
+    let folder = Folder {
+        id: layout.new_folder_id(),
+        name: name.to_string(),
+        apps: Vec::new(),
+        rules: Some(rules),
+    };
+    layout.folders.push(folder.clone());
+    layout.append(ItemRef::folder(&folder.id));
+    Ok(folder)
+}
+
+/// Move matching apps into each smart folder and apps that no longer match back onto
+/// the last page. An app matching several smart folders goes to the first one. Apps the
+/// user put in a folder of their own stay there. Returns whether anything changed.
+pub fn refresh(layout: &mut Layout, apps: &[AppFacts], now: u64, collator: &NameCollator) -> bool {
+    let before = layout.clone();
+
+    let in_user_folders: HashSet<&String> = layout
+        .folders
+        .iter()
+        .filter(|f| f.rules.is_none())
+        .flat_map(|f| &f.apps)
+        .collect();
+    let mut apps: Vec<&AppFacts> = apps
+        .iter()
+        .filter(|app| !in_user_folders.contains(&app.bundle_id))
+        .collect();
+    apps.sort_by(|a, b| collator.compare(&a.name, &b.name));
+
+    let mut claimed: HashSet<String> = HashSet::new();
+    let mut wanted: Vec<(String, Vec<String>)> = Vec::new();
+    for folder in &layout.folders {
+        let Some(rules) = &folder.rules else {
+            continue;
+        };
+        let matching: Vec<String> = apps
+            .iter()
+            .filter(|app| !claimed.contains(&app.bundle_id) && rules.matches(app, now))
+            .map(|app| app.bundle_id.clone())
+            .collect();
+        claimed.extend(matching.iter().cloned());
+        wanted.push((folder.id.clone(), matching));
+    }
+
+    for (folder_id, matching) in &wanted {
+        let leaving: Vec<String> = layout
+            .folder(folder_id)
+            .map(|f| f.apps.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|id| !matching.contains(id))
+            .collect();
+        for bundle_id in leaving {
+            if let Some(folder) = layout.folders.iter_mut().find(|f| &f.id == folder_id) {
+                folder.apps.retain(|a| *a != bundle_id);
+            }
+            layout.append(ItemRef::app(&bundle_id));
+        }
+    }
+
+    // Apps not in the layout (e.g. not synced yet) are left for sync_apps to place
+    let placed: HashSet<String> = layout.all_bundle_ids().into_iter().collect();
+    for (folder_id, matching) in &wanted {
+        for bundle_id in matching {
+            let already_in = layout
+                .folder(folder_id)
+                .is_some_and(|f| f.apps.contains(bundle_id));
+            if !already_in && placed.contains(bundle_id) {
+                if let Err(e) = layout.move_to_folder(bundle_id, folder_id, None) {
+                    eprintln!("[SmartFolders] {}", e);
+                }
+            }
+        }
+    }
+
+    layout.reflow();
+    *layout != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Page;
+
+    const NOW: u64 = 100 * DAY_SECS;
+
+    fn facts(bundle_id: &str, tags: &[&str], last_launched: Option<u64>) -> AppFacts {
+        AppFacts {
+            bundle_id: bundle_id.to_string(),
+            name: bundle_id.to_string(),
+            path: format!("/Applications/{}.app", bundle_id),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            developer: None,
+            copyright: None,
+            installed_at: None,
+            archs: Vec::new(),
+            last_launched,
+        }
+    }
+
+    fn tag_rules(tag: &str) -> SmartRules {
+        SmartRules {
+            mode: RuleMatch::All,
+            rules: vec![SmartRule::Tag {
+                tag: tag.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn refresh_leaves_user_folders_alone() {
+        let mut layout = Layout {
+            pages: vec![Page {
+                items: vec![ItemRef::folder("folder-1"), ItemRef::app("b")],
+            }],
+            folders: vec![Folder {
+                id: "folder-1".to_string(),
+                name: "Mine".to_string(),
+                apps: vec!["a".to_string()],
+                rules: None,
+            }],
+            ..Layout::default()
+        };
+        let smart = create_smart_folder(&mut layout, "Games", tag_rules("games")).unwrap();
+        let apps = [facts("a", &["games"], None), facts("b", &["games"], None)];
+
+        refresh(&mut layout, &apps, NOW, &NameCollator::for_language("en"));
+
+        assert_eq!(layout.folder("folder-1").unwrap().apps, ["a"]);
+        assert_eq!(layout.folder(&smart.id).unwrap().apps, ["b"]);
+    }
+
+    #[test]
+    fn never_launched_is_not_old() {
+        let rule = SmartRule::NotLaunchedWithin { days: 30 };
+
+        assert!(!rule.matches(&facts("a", &[], None), NOW));
+        assert!(!rule.matches(&facts("a", &[], Some(NOW - DAY_SECS)), NOW));
+        assert!(rule.matches(&facts("a", &[], Some(NOW - 40 * DAY_SECS)), NOW));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::collation::NameCollator;
+use crate::layout::{ItemRef, Layout, Page};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortKey {
+    /// A to Z
+    Name,
+    /// Newest first
+    InstallDate,
+    /// Most recently launched first
+    LastUsed,
+    /// Most launched first
+    LaunchCount,
+    /// Largest first
+    Size,
+    /// Grouped by first tag, then by name; untagged apps last
+    Tag,
+}
+
+impl SortKey {
+    /// Whether the key needs bundle sizes, which are slow to compute
+    pub fn needs_size(self) -> bool {
+        self == SortKey::Size
+    }
+}
+
+/// What part of the layout gets sorted
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SortScope {
+    /// The top-level items of one page
+    Page { page: usize },
+    /// The apps inside one folder
+    Folder { folder_id: String },
+    /// Every top-level item across pages, and the apps inside every folder
+    All,
+}
+
+/// What an app is sorted by
+#[derive(Debug, Clone, Default)]
+pub struct SortFacts {
+    pub name: String,
+    /// Seconds since the Unix epoch
+    pub installed_at: Option<u64>,
+    pub last_launched: Option<u64>,
+    pub launch_count: u64,
+    /// Bundle size in bytes
+    pub size: Option<u64>,
+    pub first_tag: Option<String>,
+}
+
+/// Sort values of a top-level item; folders take the newest/largest/total of their apps
+#[derive(Default)]
+struct ItemFacts {
+    name: String,
+    installed_at: Option<u64>,
+    last_launched: Option<u64>,
+    launch_count: u64,
+    size: Option<u64>,
+    tag: Option<String>,
+}
+
+fn app_item_facts(bundle_id: &str, facts: &HashMap<String, SortFacts>) -> ItemFacts {
+    match facts.get(bundle_id) {
+        Some(f) => ItemFacts {
+            name: f.name.clone(),
+            installed_at: f.installed_at,
+            last_launched: f.last_launched,
+            launch_count: f.launch_count,
+            size: f.size,
+            tag: f.first_tag.clone(),
+        },
+        // Apps that aren't installed anymore sort by bundle ID with no other facts
+        None => ItemFacts {
+            name: bundle_id.to_string(),
+            ..Default::default()
+        },
+    }
+}
+
+fn item_facts(layout: &Layout, item: &ItemRef, facts: &HashMap<String, SortFacts>) -> ItemFacts {
+    let folder = match item {
+        ItemRef::App { bundle_id } => return app_item_facts(bundle_id, facts),
+        ItemRef::Folder { id } => layout.folder(id),
+    };
+    let Some(folder) = folder else {
+        return ItemFacts::default();
+    };
+
+    let apps: Vec<ItemFacts> = folder
+        .apps
+        .iter()
+        .map(|id| app_item_facts(id, facts))
+        .collect();
+    let sizes: Vec<u64> = apps.iter().filter_map(|a| a.size).collect();
+
+    // A folder sorts under the tag most of its apps share
+    let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+    for tag in apps.iter().filter_map(|a| a.tag.as_deref()) {
+        *tag_counts.entry(tag).or_default() += 1;
+    }
+    let tag = tag_counts
+        .into_iter()
+        .max_by(|(a_tag, a), (b_tag, b)| a.cmp(b).then_with(|| b_tag.cmp(a_tag)))
+        .map(|(tag, _)| tag.to_string());
+
+    ItemFacts {
+        name: folder.name.clone(),
+        installed_at: apps.iter().filter_map(|a| a.installed_at).max(),
+        last_launched: apps.iter().filter_map(|a| a.last_launched).max(),
+        launch_count: apps.iter().map(|a| a.launch_count).sum(),
+        size: (!sizes.is_empty()).then(|| sizes.iter().sum()),
+        tag,
+    }
+}
+
+/// Descending by value (ascending when reversed), with missing values last either way
+fn by_value_desc(a: Option<u64>, b: Option<u64>, reverse: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if reverse => a.cmp(&b),
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Order by the key (flipped by `reverse`), then by name; items without a value for the
+/// key stay last and ties stay A to Z
+fn compare(
+    a: &ItemFacts,
+    b: &ItemFacts,
+    key: SortKey,
+    reverse: bool,
+    collator: &NameCollator,
+) -> Ordering {
+    let flip = |ordering: Ordering| {
+        if reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+    let primary = match key {
+        SortKey::Name => flip(collator.compare(&a.name, &b.name)),
+        SortKey::InstallDate => by_value_desc(a.installed_at, b.installed_at, reverse),
+        SortKey::LastUsed => by_value_desc(a.last_launched, b.last_launched, reverse),
+        SortKey::LaunchCount => flip(b.launch_count.cmp(&a.launch_count)),
+        SortKey::Size => by_value_desc(a.size, b.size, reverse),
+        SortKey::Tag => match (&a.tag, &b.tag) {
+            (Some(a), Some(b)) => flip(a.cmp(b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+    };
+    primary.then_with(|| collator.compare(&a.name, &b.name))
+}
+
+fn sort_items(
+    layout: &Layout,
+    items: &mut [ItemRef],
+    key: SortKey,
+    reverse: bool,
+    facts: &HashMap<String, SortFacts>,
+    collator: &NameCollator,
+) {
+    let mut keyed: Vec<(ItemFacts, ItemRef)> = items
+        .iter()
+        .map(|item| (item_facts(layout, item, facts), item.clone()))
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| compare(a, b, key, reverse, collator));
+    for (slot, (_, item)) in items.iter_mut().zip(keyed) {
+        *slot = item;
+    }
+}
+
+fn sort_folder_apps(
+    layout: &mut Layout,
+    folder_id: &str,
+    key: SortKey,
+    reverse: bool,
+    facts: &HashMap<String, SortFacts>,
+    collator: &NameCollator,
+) -> Result<(), String> {
+    let mut items: Vec<ItemRef> = layout
+        .folder(folder_id)
+        .ok_or_else(|| format!("Folder not found: {}", folder_id))?
+        .apps
+        .iter()
+        .map(|id| ItemRef::app(id))
+        .collect();
+    sort_items(layout, &mut items, key, reverse, facts, collator);
+
+    let apps = items
+        .into_iter()
+        .filter_map(|item| match item {
+            ItemRef::App { bundle_id } => Some(bundle_id),
+            ItemRef::Folder { .. } => None,
+        })
+        .collect();
+    if let Some(folder) = layout.folders.iter_mut().find(|f| f.id == folder_id) {
+        folder.apps = apps;
+    }
+    Ok(())
+}
+
+/// Reorder part of the layout by a sort key; `reverse` flips the key's natural direction
+pub fn sort_layout(
+    layout: &mut Layout,
+    scope: &SortScope,
+    key: SortKey,
+    reverse: bool,
+    facts: &HashMap<String, SortFacts>,
+    collator: &NameCollator,
+) -> Result<(), String> {
+    match scope {
+        SortScope::Page { page } => {
+            let mut items = layout
+                .pages
+                .get(*page)
+                .ok_or_else(|| format!("Page not found: {}", page))?
+                .items
+                .clone();
+            sort_items(layout, &mut items, key, reverse, facts, collator);
+            layout.pages[*page].items = items;
+        }
+        SortScope::Folder { folder_id } => {
+            sort_folder_apps(layout, folder_id, key, reverse, facts, collator)?;
+        }
+        SortScope::All => {
+            let folder_ids: Vec<String> = layout.folders.iter().map(|f| f.id.clone()).collect();
+            for folder_id in folder_ids {
+                sort_folder_apps(layout, &folder_id, key, reverse, facts, collator)?;
+            }
+
+            // Sorted items fill the pages from the first one
+            let mut items: Vec<ItemRef> = layout.pages.drain(..).flat_map(|p| p.items).collect();
+            sort_items(layout, &mut items, key, reverse, facts, collator);
+            layout.pages = vec![Page { items }];
+            layout.reflow();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts(name: &str, size: Option<u64>) -> (String, SortFacts) {
+        let facts = SortFacts {
+            name: name.to_string(),
+            size,
+            ..Default::default()
+        };
+        (name.to_string(), facts)
+    }
+
+    fn sorted(key: SortKey, reverse: bool) -> Vec<String> {
+        let facts: HashMap<String, SortFacts> = [
+            facts("Delta", None),
+            facts("Bravo", Some(20)),
+            facts("Alpha", Some(10)),
+            facts("Charlie", Some(20)),
+            facts("Echo", None),
+        ]
+        .into_iter()
+        .collect();
+        let mut layout = Layout {
+            pages: vec![Page {
+                items: ["Delta", "Bravo", "Alpha", "Charlie", "Echo"]
+                    .iter()
+                    .map(|id| ItemRef::app(id))
+                    .collect(),
+            }],
+            ..Default::default()
+        };
+
+        let collator = NameCollator::for_language("en");
+        sort_layout(
+            &mut layout,
+            &SortScope::All,
+            key,
+            reverse,
+            &facts,
+            &collator,
+        )
+        .unwrap();
+        layout.pages[0]
+            .items
+            .iter()
+            .map(|item| match item {
+                ItemRef::App { bundle_id } => bundle_id.clone(),
+                ItemRef::Folder { id } => id.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reverse_flips_only_the_sort_key() {
+        assert_eq!(
+            sorted(SortKey::Size, false),
+            ["Bravo", "Charlie", "Alpha", "Delta", "Echo"]
+        );
+        // Smallest first, but equal sizes stay A to Z and apps without a size stay last
+        assert_eq!(
+            sorted(SortKey::Size, true),
+            ["Alpha", "Bravo", "Charlie", "Delta", "Echo"]
+        );
+        assert_eq!(
+            sorted(SortKey::Name, true),
+            ["Echo", "Delta", "Charlie", "Bravo", "Alpha"]
+        );
+    }
+}
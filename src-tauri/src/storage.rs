@@ -0,0 +1,70 @@
+use directories::ProjectDirs;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Get the app data directory (backend-owned state lives here), creating it if it doesn't exist
+pub fn data_dir() -> Option<PathBuf> {
+    let proj_dirs = ProjectDirs::from("com", "launchpad", "Launchpad")?;
+    let data_dir = proj_dirs.data_dir().to_path_buf();
+    if !data_dir.exists() {
+        fs::create_dir_all(&data_dir).ok()?;
+    }
+    Some(data_dir)
+}
+
+/// Load a JSON file from the data directory.
+/// Returns None if the file doesn't exist or can't be parsed.
+pub fn load_json<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    let path = data_dir()?.join(file_name);
+    let contents = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            eprintln!("[Storage] Failed to parse {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Save a value as JSON into the data directory
+pub fn save_json<T: Serialize>(file_name: &str, value: &T) -> Result<(), String> {
+    let dir = data_dir().ok_or("Failed to resolve app data directory")?;
+    write_json_atomic(&dir.join(file_name), value)
+}
+
+/// Remove a file from the data directory (no error if it doesn't exist)
+pub fn remove_file(file_name: &str) -> Result<(), String> {
+    let dir = data_dir().ok_or("Failed to resolve app data directory")?;
+    let path = dir.join(file_name);
+    if !path.exists() {
+        return Ok(());
+    }
+    fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
+
+/// Write JSON to a temp file next to the target and rename it into place,
+/// so a crash mid-write never leaves a truncated file behind
+pub fn write_json_atomic<T: Serialize>(path: &std::path::Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid file name")?;
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let mut file = fs::File::create(&temp_path)
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    file.write_all(&json)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+
+    fs::rename(&temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })
+}
@@ -0,0 +1,493 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+const TAG_STORE_FILE: &str = "tags.json";
+
+/// Icon used for custom tags created from Finder tags
+const FINDER_TAG_ICON: &str = "TagIcon";
+
+/// Keys of the built-in category tags (PREDEFINED_TAGS in the frontend)
+const BUILTIN_TAGS: &[&str] = &[
+    "dev-tools",
+    "social",
+    "browsers",
+    "utilities",
+    "entertainment",
+    "creativity",
+    "planning",
+    "office",
+];
+
+/// Derive a custom tag key from its label the same way the frontend does ("My Tag" -> "custom-my-tag").
+/// Labels without ASCII letters or digits ("Работа") keep the letters and digits of their own
+/// script so non-Latin Finder tags get a key too; other labels keep the ASCII-only key.
+pub fn tag_key_from_label(label: &str) -> Option<String> {
+    let words = label
+        .trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug = |keep: fn(&char) -> bool| -> String {
+        words.chars().filter(|c| keep(c) || *c == '-').collect()
+    };
+
+    let ascii = slug(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    let slug = if ascii.chars().any(|c| c != '-') {
+        ascii
+    } else {
+        slug(|c| c.is_alphanumeric())
+    };
+
+    if slug.is_empty() {
+        None
+    } else {
+        Some(format!("custom-{}", slug))
+    }
+}
+
+/// A user-created tag (built-in tags like "dev-tools" are defined by the frontend)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomTag {
+    pub key: String,
+    pub label: String,
+    #[serde(alias = "iconName")]
+    pub icon_name: String,
+}
+
+/// Manual changes on top of the detected tags for one app.
+/// Kept as a diff so re-detection can still update the tags the user never touched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagOverride {
+    #[serde(default)]
+    pub added: BTreeSet<String>,
+    #[serde(default)]
+    pub removed: BTreeSet<String>,
+}
+
+impl TagOverride {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Persisted tag assignments and custom tags, keyed by bundle ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TagStore {
+    #[serde(default)]
+    custom_tags: Vec<CustomTag>,
+    /// Tags from the last scan (detect_app_tags) - rewritten on every scan
+    #[serde(default)]
+    detected: BTreeMap<String, Vec<String>>,
+    /// Tags read from the Finder tags xattr during the last scan - rewritten on every scan
+    #[serde(default)]
+    finder: BTreeMap<String, Vec<String>>,
+    /// User overrides - never touched by scanning
+    #[serde(default)]
+    overrides: BTreeMap<String, TagOverride>,
+    /// Whether the old localStorage keys have been imported
+    #[serde(default)]
+    legacy_imported: bool,
+    /// Imported localStorage tag lists of apps not detected yet, turned into overrides
+    /// once detection has run for them
+    #[serde(default)]
+    pending_legacy: BTreeMap<String, Vec<String>>,
+    /// Write custom tag changes back to the Finder tags of the bundle
+    #[serde(default)]
+    write_finder_tags: bool,
+    /// Bundle paths from the last scan, needed to write Finder tags back
+    #[serde(skip)]
+    paths: BTreeMap<String, String>,
+}
+
+impl TagStore {
+    /// Load the store from the app data dir (empty store if missing or unreadable)
+    pub fn load() -> Self {
+        storage::load_json(TAG_STORE_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(TAG_STORE_FILE, self)
+    }
+
+    /// Record the tags detected by a scan. Returns true if anything changed.
+    pub fn set_detected(&mut self, bundle_id: &str, tags: &[String]) -> bool {
+        if self.detected.get(bundle_id).map(|t| t.as_slice()) == Some(tags) {
+            return false;
+        }
+        self.detected.insert(bundle_id.to_string(), tags.to_vec());
+        if let Some(legacy) = self.pending_legacy.remove(bundle_id) {
+            self.apply_legacy(bundle_id, &legacy);
+        }
+        true
+    }
+
+    /// Record the Finder tags of a bundle, creating custom tags for names we haven't seen.
+    /// Returns true if anything changed.
+    pub fn set_finder_tags(&mut self, bundle_id: &str, path: &str, names: &[String]) -> bool {
+        self.paths.insert(bundle_id.to_string(), path.to_string());
+
+        let mut changed = false;
+        let mut keys = Vec::new();
+        for name in names {
+            let key = match self.custom_tag_for_finder_name(name) {
+                Some(tag) => tag.key.clone(),
+                None => {
+                    let Some(key) = tag_key_from_label(name) else {
+                        continue;
+                    };
+                    self.custom_tags.push(CustomTag {
+                        key: key.clone(),
+                        label: name.clone(),
+                        icon_name: FINDER_TAG_ICON.to_string(),
+                    });
+                    changed = true;
+                    key
+                }
+            };
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let previous = self.finder.get(bundle_id).cloned().unwrap_or_default();
+        if previous != keys {
+            if keys.is_empty() {
+                self.finder.remove(bundle_id);
+            } else {
+                self.finder.insert(bundle_id.to_string(), keys);
+            }
+            self.prune_override(bundle_id);
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Find the custom tag a Finder tag name belongs to (by label, then by derived key)
+    fn custom_tag_for_finder_name(&self, name: &str) -> Option<&CustomTag> {
+        self.custom_tags
+            .iter()
+            .find(|t| t.label.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                let key = tag_key_from_label(name)?;
+                self.custom_tags.iter().find(|t| t.key == key)
+            })
+    }
+
+    pub fn finder_sync_enabled(&self) -> bool {
+        self.write_finder_tags
+    }
+
+    pub fn set_finder_sync(&mut self, enabled: bool) {
+        self.write_finder_tags = enabled;
+    }
+
+    /// Bundle path and the Finder tag names it should carry: the labels of its custom tags.
+    /// Built-in category tags are derived on every scan and are never written to Finder.
+    pub fn finder_names_for(&self, bundle_id: &str) -> Option<(String, Vec<String>)> {
+        let path = self.paths.get(bundle_id)?.clone();
+        let names = self
+            .tags_for(bundle_id)
+            .iter()
+            .filter_map(|key| self.custom_tags.iter().find(|t| &t.key == key))
+            .map(|t| t.label.clone())
+            .collect();
+        Some((path, names))
+    }
+
+    /// Finder tag names Launchpad manages: the labels of all custom tags
+    pub fn managed_finder_names(&self) -> Vec<String> {
+        self.custom_tags.iter().map(|t| t.label.clone()).collect()
+    }
+
+    pub fn custom_tag_label(&self, key: &str) -> Option<String> {
+        self.custom_tags
+            .iter()
+            .find(|t| t.key == key)
+            .map(|t| t.label.clone())
+    }
+
+    /// Effective tags for an app: detected and Finder tags plus manual additions, minus manual removals
+    pub fn tags_for(&self, bundle_id: &str) -> Vec<String> {
+        let mut tags: Vec<String> = self.detected.get(bundle_id).cloned().unwrap_or_default();
+        for tag in self.finder.get(bundle_id).into_iter().flatten() {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+
+        if let Some(over) = self.overrides.get(bundle_id) {
+            tags.retain(|t| !over.removed.contains(t));
+            for tag in &over.added {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+
+        tags
+    }
+
+    /// Effective tags for every known app
+    pub fn assignments(&self) -> BTreeMap<String, Vec<String>> {
+        self.detected
+            .keys()
+            .chain(self.finder.keys())
+            .chain(self.overrides.keys())
+            .map(|bundle_id| (bundle_id.clone(), self.tags_for(bundle_id)))
+            .collect()
+    }
+
+    /// Bundle IDs of all apps carrying a tag
+    pub fn apps_with_tag(&self, tag_key: &str) -> Vec<String> {
+        self.assignments()
+            .into_iter()
+            .filter(|(_, tags)| tags.iter().any(|t| t == tag_key))
+            .map(|(bundle_id, _)| bundle_id)
+            .collect()
+    }
+
+    /// Number of apps carrying each tag
+    pub fn tag_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for tags in self.assignments().values() {
+            for tag in tags {
+                *counts.entry(tag.clone()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Whether a key names a built-in tag or an existing custom tag
+    pub fn is_known_tag(&self, tag_key: &str) -> bool {
+        BUILTIN_TAGS.contains(&tag_key) || self.custom_tags.iter().any(|t| t.key == tag_key)
+    }
+
+    pub fn assign(&mut self, bundle_id: &str, tag_key: &str) -> Result<(), String> {
+        if !self.is_known_tag(tag_key) {
+            return Err(format!("Unknown tag: {}", tag_key));
+        }
+        self.add_override(bundle_id, tag_key);
+        Ok(())
+    }
+
+    fn add_override(&mut self, bundle_id: &str, tag_key: &str) {
+        let automatic = self.is_automatic(bundle_id, tag_key);
+        let over = self.overrides.entry(bundle_id.to_string()).or_default();
+        over.removed.remove(tag_key);
+        if !automatic {
+            over.added.insert(tag_key.to_string());
+        }
+
+        self.prune_override(bundle_id);
+    }
+
+    pub fn unassign(&mut self, bundle_id: &str, tag_key: &str) {
+        let automatic = self.is_automatic(bundle_id, tag_key);
+        let over = self.overrides.entry(bundle_id.to_string()).or_default();
+        over.added.remove(tag_key);
+        if automatic {
+            over.removed.insert(tag_key.to_string());
+        }
+
+        self.prune_override(bundle_id);
+    }
+
+    /// Whether a tag comes from detection or Finder rather than from a manual choice
+    fn is_automatic(&self, bundle_id: &str, tag_key: &str) -> bool {
+        let has = |map: &BTreeMap<String, Vec<String>>| {
+            map.get(bundle_id)
+                .map(|t| t.iter().any(|k| k == tag_key))
+                .unwrap_or(false)
+        };
+        has(&self.detected) || has(&self.finder)
+    }
+
+    /// Drop override entries that no longer change anything
+    fn prune_override(&mut self, bundle_id: &str) {
+        let Some(over) = self.overrides.get(bundle_id) else {
+            return;
+        };
+
+        let added: BTreeSet<String> = over
+            .added
+            .iter()
+            .filter(|t| !self.is_automatic(bundle_id, t))
+            .cloned()
+            .collect();
+        let removed: BTreeSet<String> = over
+            .removed
+            .iter()
+            .filter(|t| self.is_automatic(bundle_id, t))
+            .cloned()
+            .collect();
+
+        if added.is_empty() && removed.is_empty() {
+            self.overrides.remove(bundle_id);
+        } else {
+            self.overrides
+                .insert(bundle_id.to_string(), TagOverride { added, removed });
+        }
+    }
+
+    pub fn custom_tags(&self) -> &[CustomTag] {
+        &self.custom_tags
+    }
+
+    /// Create a custom tag. The key is derived from the label the same way the frontend does it.
+    pub fn create_tag(&mut self, label: &str, icon_name: &str) -> Result<CustomTag, String> {
+        let label = label.trim();
+        let key = tag_key_from_label(label)
+            .ok_or_else(|| "Tag name must contain letters or digits".to_string())?;
+
+        let tag = CustomTag {
+            key,
+            label: label.to_string(),
+            icon_name: icon_name.to_string(),
+        };
+
+        // Re-creating an existing tag updates it instead of duplicating
+        match self.custom_tags.iter_mut().find(|t| t.key == tag.key) {
+            Some(existing) => *existing = tag.clone(),
+            None => self.custom_tags.push(tag.clone()),
+        }
+
+        Ok(tag)
+    }
+
+    pub fn rename_tag(&mut self, key: &str, label: &str) -> Result<(), String> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("Tag name cannot be empty".to_string());
+        }
+
+        let tag = self
+            .custom_tags
+            .iter_mut()
+            .find(|t| t.key == key)
+            .ok_or_else(|| format!("Unknown custom tag: {}", key))?;
+        tag.label = label.to_string();
+        Ok(())
+    }
+
+    /// Delete a custom tag and remove it from every app
+    pub fn delete_tag(&mut self, key: &str) -> Result<(), String> {
+        let before = self.custom_tags.len();
+        self.custom_tags.retain(|t| t.key != key);
+        if self.custom_tags.len() == before {
+            return Err(format!("Unknown custom tag: {}", key));
+        }
+
+        for over in self.overrides.values_mut() {
+            over.added.remove(key);
+            over.removed.remove(key);
+        }
+        self.overrides.retain(|_, o| !o.is_empty());
+
+        for keys in self.finder.values_mut() {
+            keys.retain(|k| k != key);
+        }
+        self.finder.retain(|_, keys| !keys.is_empty());
+
+        Ok(())
+    }
+
+    /// Drop all custom tags and manual changes, leaving every app with its detected tags
+    pub fn reset(&mut self) {
+        self.custom_tags.clear();
+        self.overrides.clear();
+        self.pending_legacy.clear();
+        self.finder.clear();
+    }
+
+    /// Take over custom tags and per-app tag lists from a layout file.
+    /// With `keep_local` tags are only added, otherwise each listed app ends up with exactly these tags.
+    pub fn import_tags(
+        &mut self,
+        custom_tags: &[CustomTag],
+        assignments: &BTreeMap<String, Vec<String>>,
+        keep_local: bool,
+    ) {
+        for tag in custom_tags {
+            match self.custom_tags.iter_mut().find(|t| t.key == tag.key) {
+                None => self.custom_tags.push(tag.clone()),
+                Some(existing) if !keep_local => *existing = tag.clone(),
+                // Local label and icon win when merging
+                Some(_) => {}
+            }
+        }
+
+        for (bundle_id, tags) in assignments {
+            if !keep_local {
+                for current in self.tags_for(bundle_id) {
+                    if !tags.contains(&current) {
+                        self.unassign(bundle_id, &current);
+                    }
+                }
+            }
+            // Files from other machines can name custom tags that were never exported
+            for tag in tags {
+                if self.is_known_tag(tag) {
+                    self.add_override(bundle_id, tag);
+                }
+            }
+        }
+    }
+
+    /// One-time import of the `launchpad-tag-assignments` and `launchpad-custom-tags`
+    /// localStorage keys. Returns false if the import already happened.
+    pub fn import_legacy(
+        &mut self,
+        assignments: BTreeMap<String, Vec<String>>,
+        custom_tags: Vec<CustomTag>,
+    ) -> bool {
+        if self.legacy_imported {
+            return false;
+        }
+
+        for tag in custom_tags {
+            match self.custom_tags.iter_mut().find(|t| t.key == tag.key) {
+                Some(existing) => *existing = tag,
+                None => self.custom_tags.push(tag),
+            }
+        }
+
+        // Diffing against apps that haven't been detected yet would turn every
+        // legacy tag into a manual override, so those wait for their first scan
+        for (bundle_id, tags) in assignments {
+            if self.detected.contains_key(&bundle_id) {
+                self.apply_legacy(&bundle_id, &tags);
+            } else {
+                self.pending_legacy.insert(bundle_id, tags);
+            }
+        }
+
+        self.legacy_imported = true;
+        true
+    }
+
+    /// The frontend stored full tag lists; keep only the difference from detection
+    fn apply_legacy(&mut self, bundle_id: &str, tags: &[String]) {
+        let detected = self.detected.get(bundle_id).cloned().unwrap_or_default();
+        let over = TagOverride {
+            added: tags
+                .iter()
+                .filter(|t| !detected.contains(t) && self.is_known_tag(t))
+                .cloned()
+                .collect(),
+            removed: detected
+                .iter()
+                .filter(|t| !tags.contains(t))
+                .cloned()
+                .collect(),
+        };
+        if over.is_empty() {
+            self.overrides.remove(bundle_id);
+        } else {
+            self.overrides.insert(bundle_id.to_string(), over);
+        }
+        self.prune_override(bundle_id);
+    }
+}
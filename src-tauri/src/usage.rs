@@ -0,0 +1,51 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const USAGE_FILE: &str = "usage.json";
+
+/// Launches of one app from Launchpad
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppUsage {
+    #[serde(default)]
+    pub launch_count: u64,
+    /// Seconds since the Unix epoch
+    #[serde(default)]
+    pub last_launched: Option<u64>,
+}
+
+/// Launch statistics keyed by bundle ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UsageStore {
+    #[serde(default)]
+    apps: BTreeMap<String, AppUsage>,
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl UsageStore {
+    /// Load the store from the app data dir (empty store if missing or unreadable)
+    pub fn load() -> Self {
+        storage::load_json(USAGE_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save_json(USAGE_FILE, self)
+    }
+
+    pub fn record_launch(&mut self, bundle_id: &str) {
+        let usage = self.apps.entry(bundle_id.to_string()).or_default();
+        usage.launch_count += 1;
+        usage.last_launched = Some(now_secs());
+    }
+
+    pub fn get(&self, bundle_id: &str) -> Option<&AppUsage> {
+        self.apps.get(bundle_id)
+    }
+}
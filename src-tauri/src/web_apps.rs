@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Chromium-based browsers that can install web apps, by the bundle ID prefix of their web app shims
+const CHROMIUM_HOSTS: &[(&str, &str)] = &[
+    ("com.google.chrome.", "chrome"),
+    ("com.microsoft.edgemac.", "edge"),
+    ("com.brave.browser.", "brave"),
+    ("com.vivaldi.vivaldi.", "vivaldi"),
+    ("com.operasoftware.opera.", "opera"),
+    ("org.chromium.chromium.", "chromium"),
+];
+
+/// Safari "Add to Dock" web apps use bundle IDs under this prefix
+const SAFARI_WEB_APP_PREFIX: &str = "com.apple.safari.webapp";
+
+/// Sites mapped to the tag they belong to (matched against the host and its parent domains)
+const SITE_TAGS: &[(&str, &str)] = &[
+    // Office
+    ("docs.google.com", "office"),
+    ("sheets.google.com", "office"),
+    ("slides.google.com", "office"),
+    ("drive.google.com", "office"),
+    ("office.com", "office"),
+    ("notion.so", "office"),
+    ("airtable.com", "office"),
+    ("coda.io", "office"),
+    // Social
+    ("mail.google.com", "social"),
+    ("outlook.live.com", "social"),
+    ("outlook.office.com", "social"),
+    ("meet.google.com", "social"),
+    ("chat.google.com", "social"),
+    ("web.whatsapp.com", "social"),
+    ("web.telegram.org", "social"),
+    ("messenger.com", "social"),
+    ("slack.com", "social"),
+    ("discord.com", "social"),
+    ("teams.microsoft.com", "social"),
+    ("x.com", "social"),
+    ("twitter.com", "social"),
+    ("facebook.com", "social"),
+    ("instagram.com", "social"),
+    ("linkedin.com", "social"),
+    ("bsky.app", "social"),
+    // Planning
+    ("calendar.google.com", "planning"),
+    ("keep.google.com", "planning"),
+    ("todoist.com", "planning"),
+    ("trello.com", "planning"),
+    ("asana.com", "planning"),
+    ("linear.app", "planning"),
+    ("clickup.com", "planning"),
+    // Dev Tools
+    ("github.com", "dev-tools"),
+    ("gitlab.com", "dev-tools"),
+    ("bitbucket.org", "dev-tools"),
+    ("vercel.com", "dev-tools"),
+    ("codesandbox.io", "dev-tools"),
+    ("stackblitz.com", "dev-tools"),
+    // Creativity
+    ("figma.com", "creativity"),
+    ("canva.com", "creativity"),
+    ("photopea.com", "creativity"),
+    ("excalidraw.com", "creativity"),
+    // Entertainment
+    ("youtube.com", "entertainment"),
+    ("music.youtube.com", "entertainment"),
+    ("open.spotify.com", "entertainment"),
+    ("netflix.com", "entertainment"),
+    ("twitch.tv", "entertainment"),
+    ("music.apple.com", "entertainment"),
+    ("primevideo.com", "entertainment"),
+    ("disneyplus.com", "entertainment"),
+];
+
+/// What kind of item a scanned bundle is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppKind {
+    #[default]
+    App,
+    WebApp,
+}
+
+/// Details of a web app shim installed by a browser
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebAppInfo {
+    pub url: Option<String>,
+    pub host_browser: String,
+}
+
+/// Recognize Safari and Chromium web app shims and extract the site they open
+pub fn detect_web_app(
+    app_path: &Path,
+    plist_dict: &plist::Dictionary,
+    bundle_id: &str,
+) -> Option<WebAppInfo> {
+    let bundle_lower = bundle_id.to_lowercase();
+
+    if bundle_lower.starts_with(SAFARI_WEB_APP_PREFIX) {
+        return Some(WebAppInfo {
+            url: safari_web_app_url(app_path, plist_dict),
+            host_browser: "safari".to_string(),
+        });
+    }
+
+    // Chromium shims look like "com.google.Chrome.app.<id>" and carry the site in CrAppModeShortcutURL
+    let chromium_url = plist_dict
+        .get("CrAppModeShortcutURL")
+        .and_then(|v| v.as_string())
+        .map(|s| s.to_string());
+
+    let host = CHROMIUM_HOSTS
+        .iter()
+        .find(|(prefix, _)| bundle_lower.starts_with(prefix) && bundle_lower.contains(".app."))
+        .map(|(_, browser)| browser.to_string())
+        .or_else(|| {
+            // Unknown Chromium fork - fall back to the browser bundle ID the shim points at
+            chromium_url.as_ref()?;
+            plist_dict
+                .get("CrBundleIdentifier")
+                .and_then(|v| v.as_string())
+                .map(|s| s.to_lowercase())
+        })?;
+
+    Some(WebAppInfo {
+        url: chromium_url,
+        host_browser: host,
+    })
+}
+
+/// Safari web apps keep their manifest (start_url / scope) either in Info.plist
+/// or as a JSON manifest in Contents/Resources
+fn safari_web_app_url(app_path: &Path, plist_dict: &plist::Dictionary) -> Option<String> {
+    const URL_KEYS: &[&str] = &["start_url", "scope", "id"];
+
+    if let Some(manifest) = plist_dict.get("Manifest").and_then(|v| v.as_dictionary()) {
+        for key in URL_KEYS {
+            if let Some(url) = manifest.get(key).and_then(|v| v.as_string()) {
+                return Some(url.to_string());
+            }
+        }
+    }
+
+    let entries = fs::read_dir(app_path.join("Contents/Resources")).ok()?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&contents) else {
+            continue;
+        };
+
+        for key in URL_KEYS {
+            if let Some(url) = manifest.get(key).and_then(|v| v.as_str()) {
+                if url.starts_with("http") {
+                    return Some(url.to_string());
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Extract the host from a URL ("https://mail.google.com/mail/u/0" -> "mail.google.com")
+pub fn url_host(url: &str) -> Option<String> {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let host = without_scheme
+        .split(['/', '?', '#'])
+        .next()?
+        .rsplit('@')
+        .next()?
+        .split(':')
+        .next()?
+        .trim_start_matches("www.")
+        .to_lowercase();
+
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+/// Tag a web app by the site it opens, not by the browser hosting it
+pub fn detect_web_app_tags(info: &WebAppInfo, name: &str) -> Vec<String> {
+    if let Some(host) = info.url.as_deref().and_then(url_host) {
+        // Most specific match first: "mail.google.com" before "google.com"
+        let mut domain = host.as_str();
+        loop {
+            if let Some((_, tag)) = SITE_TAGS.iter().find(|(site, _)| *site == domain) {
+                return vec![tag.to_string()];
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => domain = parent,
+                _ => break,
+            }
+        }
+    }
+
+    // Fall back to the site name (e.g. "Gmail"), but a web app is never a browser itself
+    match crate::detect_tag_from_app_name(name, "") {
+        Some(tag) if tag != "browsers" => vec![tag.to_string()],
+        _ => Vec::new(),
+    }
+}
@@ -3,8 +3,16 @@ import { useTranslation } from "react-i18next";
 import "./App.css";
 
 // Type imports
-import type { GridSettings as GridSettingsType, Tag, App } from "./types";
-import { isFolder } from "./types";
+import type {
+  BackendSettings,
+  GridSettings as GridSettingsType,
+  Tag,
+  App,
+  CustomTagDefinition,
+  LaunchpadItem,
+  LayoutItemRef,
+} from "./types";
+import { isFolder, toItemRef } from "./types";
 
 // Constants
 import { PREDEFINED_TAGS, getIconByName } from "./constants/tags";
@@ -39,35 +47,70 @@ import { AboutDialog } from "./components/AboutDialog";
 import { CreateTagModal } from "./components/CreateTagModal";
 import { Button } from "./components/ui/Button";
 
+// Renamed apps are found under both their custom and their original name
+function appMatchesSearch(app: App, query: string): boolean {
+  const needle = query.toLowerCase();
+  return (
+    app.name.toLowerCase().includes(needle) ||
+    !!app.original_name?.toLowerCase().includes(needle)
+  );
+}
+
+function toGridSettings(settings: BackendSettings): GridSettingsType {
+  return {
+    rows: settings.rows,
+    cols: settings.cols,
+    fullWidth: settings.full_width,
+    hotCornerEnabled: settings.hot_corner.enabled,
+    hotCorner: settings.hot_corner.corner,
+    hotCornerThreshold: settings.hot_corner.threshold,
+    hotCornerDebounce: settings.hot_corner.debounce_ms,
+    globalShortcut: settings.global_shortcut,
+    tagSettings: {
+      showTagBar: settings.tags.show_tag_bar,
+      autoTagNewApps: settings.tags.auto_tag_new_apps,
+      customTags: [],
+    },
+  };
+}
+
+function toBackendSettings(grid: GridSettingsType, current: BackendSettings): BackendSettings {
+  return {
+    ...current,
+    rows: grid.rows,
+    cols: grid.cols,
+    full_width: grid.fullWidth,
+    hot_corner: {
+      enabled: grid.hotCornerEnabled,
+      corner: grid.hotCorner,
+      threshold: grid.hotCornerThreshold,
+      debounce_ms: grid.hotCornerDebounce,
+    },
+    global_shortcut: grid.globalShortcut,
+    tags: {
+      show_tag_bar: grid.tagSettings.showTagBar,
+      auto_tag_new_apps: grid.tagSettings.autoTagNewApps,
+    },
+  };
+}
+
 function App() {
-  const { t } = useTranslation();
+  const { t, i18n } = useTranslation();
 
   // App management state and functions
   const {
     apps,
-    items,
+    pages: layoutPages,
+    layout,
     loading,
     error,
-    setItems,
     launchApp,
+    launchError,
+    dismissLaunchError,
     loadApps,
-    saveItemOrder,
-    mergeAppsAndFolders,
-    createSystemFolders,
+    loadLayout,
   } = useAppManagement();
 
-  // Folder management state and functions
-  const {
-    folders,
-    openFolder,
-    setFolders,
-    setOpenFolder,
-    saveFolders,
-    createFolder,
-    deleteFolder,
-    updateFolderName,
-  } = useFolderManagement();
-
   // UI state
   const [searchQuery, setSearchQuery] = useState("");
   const [currentPage, setCurrentPage] = useState(0);
@@ -78,6 +121,7 @@ function App() {
     appName: string;
     appPath: string;
     bundleId: string;
+    launchPresets: string[];
   } | null>(null);
   // Settings modals state
   const [showLanguageSettings, setShowLanguageSettings] = useState(false);
@@ -101,63 +145,59 @@ function App() {
   const [selectedTag, setSelectedTag] = useState<string | null>(null);
   const [availableTags, setAvailableTags] = useState<Tag[]>([]);
 
-  // Tag assignments persistence (bundleId -> array of tag keys)
-  const [tagAssignments, setTagAssignments] = useState<Record<string, string[]>>(() => {
-    const saved = localStorage.getItem("launchpad-tag-assignments");
-    return saved ? JSON.parse(saved) : {};
-  });
-  const [customTags, setCustomTags] = useState<{
-    key: string;
-    label: string;
-    iconName: string;
-  }[]>(() => {
-    const saved = localStorage.getItem("launchpad-custom-tags");
-    if (!saved) return [];
-
-    // Deduplicate tags by key (keep the last occurrence)
-    const parsed = JSON.parse(saved) as { key: string; label: string; iconName: string }[];
-    const uniqueByKey = new Map<string, { key: string; label: string; iconName: string }>();
-    parsed.forEach(tag => uniqueByKey.set(tag.key, tag));
-    const deduplicated = Array.from(uniqueByKey.values());
-
-    // Save deduplicated version back if there were duplicates
-    if (deduplicated.length !== parsed.length) {
-      localStorage.setItem("launchpad-custom-tags", JSON.stringify(deduplicated));
-    }
-
-    return deduplicated;
-  });
-
-  // Grid settings
-  const [gridSettings, setGridSettings] = useState<GridSettingsType>(() => {
-    const saved = localStorage.getItem("launchpad-grid-settings");
-    const defaults = {
-      rows: 7,
-      cols: 10,
-      fullWidth: false,
-      hotCornerEnabled: false,
-      hotCorner: "top-left",
-      hotCornerThreshold: 10,
-      hotCornerDebounce: 5000,
-      globalShortcut: "F4",
-      tagSettings: {
-        showTagBar: true,
-        autoTagNewApps: true,
-        customTags: [],
-      },
-    };
+  // Tags changed since the apps were loaded (bundleId -> array of tag keys). The backend
+  // owns the tags; apps arrive with their effective tags already applied.
+  const [tagAssignments, setTagAssignments] = useState<Record<string, string[]>>({});
+  const [customTags, setCustomTags] = useState<CustomTagDefinition[]>([]);
 
-    if (saved) {
-      const parsed = JSON.parse(saved);
-      // Merge with defaults to ensure all properties exist
-      return { ...defaults, ...parsed };
-    }
+  // Pages of the grid from the backend layout, with tag changes since the last scan applied
+  const pages = useMemo(() => {
+    const withTags = (app: App) => ({ ...app, tags: tagAssignments[app.bundle_id] ?? app.tags ?? [] });
+    return layoutPages.map((page) =>
+      page.map((item) => (isFolder(item) ? { ...item, apps: item.apps.map(withTags) } : withTags(item))),
+    );
+  }, [layoutPages, tagAssignments]);
+  const items = useMemo(() => pages.flat(), [pages]);
 
-    return defaults;
+  // Folder management state and functions
+  const { openFolder, setOpenFolder, createFolder, deleteFolder, updateFolderName } =
+    useFolderManagement(items);
+  // Folder to show once the layout has it (a new folder goes on the last page)
+  const [revealFolderId, setRevealFolderId] = useState<string | null>(null);
+
+  // Grid settings, edited in the settings screens and saved through the backend
+  const [gridSettings, setGridSettings] = useState<GridSettingsType>({
+    rows: 7,
+    cols: 10,
+    fullWidth: false,
+    hotCornerEnabled: false,
+    hotCorner: "top-left",
+    hotCornerThreshold: 10,
+    hotCornerDebounce: 5000,
+    globalShortcut: "F4",
+    tagSettings: {
+      showTagBar: true,
+      autoTagNewApps: true,
+      customTags: [],
+    },
   });
+  // Last settings loaded from the backend; fields the screens don't edit are sent back as is
+  const backendSettingsRef = useRef<BackendSettings | null>(null);
 
   const APPS_PER_PAGE = gridSettings.rows * gridSettings.cols;
   const containerRef = useRef<HTMLDivElement>(null);
+  const isFiltering = !!searchQuery || !!selectedTag;
+
+  // Index in items of the first item of each layout page
+  const pageStarts = useMemo(() => {
+    const starts: number[] = [];
+    let start = 0;
+    for (const page of pages) {
+      starts.push(start);
+      start += page.length;
+    }
+    return starts;
+  }, [pages]);
 
   // Drag and drop functionality
   const {
@@ -174,30 +214,15 @@ function App() {
     handleDrop,
   } = useDragAndDrop({
     items,
-    setItems,
-    folders,
-    setFolders,
     openFolder,
-    setOpenFolder,
     currentPage,
     setCurrentPage,
-    appsPerPage: APPS_PER_PAGE,
-    filteredItems: searchQuery
-      ? items.filter((item) => {
-          if (isFolder(item)) {
-            return (
-              item.name.toLowerCase().includes(searchQuery.toLowerCase()) ||
-              item.apps.some((app) => app.name.toLowerCase().includes(searchQuery.toLowerCase()))
-            );
-          } else {
-            return item.name.toLowerCase().includes(searchQuery.toLowerCase());
-          }
-        })
-      : items,
-    isFiltering: !!searchQuery || !!selectedTag,
+    pageStarts,
+    totalPages: pages.length,
+    isFiltering,
     gridSettings,
-    saveItemOrder,
-    saveFolders,
+    onMoveItem: moveItem,
+    onMergeItems: mergeItems,
   });
 
   // Apply tag filtering and search filtering
@@ -223,12 +248,10 @@ function App() {
         if (isFolder(item)) {
           return (
             item.name.toLowerCase().includes(searchQuery.toLowerCase()) ||
-            item.apps.some((app) =>
-              app.name.toLowerCase().includes(searchQuery.toLowerCase()),
-            )
+            item.apps.some((app) => appMatchesSearch(app, searchQuery))
           );
         } else {
-          return item.name.toLowerCase().includes(searchQuery.toLowerCase());
+          return appMatchesSearch(item, searchQuery);
         }
       });
     }
@@ -247,10 +270,12 @@ function App() {
     return filteredItems;
   })();
 
-  // Pagination
-  const totalPages = Math.ceil(displayItems.length / APPS_PER_PAGE);
-  const startIndex = currentPage * APPS_PER_PAGE;
-  const endIndex = startIndex + APPS_PER_PAGE;
+  // Pagination: the layout's pages, or pages of the grid size while filtering by tag
+  const totalPages = isFiltering
+    ? Math.ceil(displayItems.length / APPS_PER_PAGE)
+    : pages.length;
+  const startIndex = isFiltering ? currentPage * APPS_PER_PAGE : (pageStarts[currentPage] ?? 0);
+  const endIndex = startIndex + (isFiltering ? APPS_PER_PAGE : (pages[currentPage]?.length ?? 0));
   const currentItems = searchQuery ? displayItems : displayItems.slice(startIndex, endIndex);
 
   // Settings modal helpers
@@ -280,8 +305,7 @@ function App() {
 
   // Keyboard navigation
   useKeyboardNavigation({
-    filteredItemsLength: filteredItems.length,
-    appsPerPage: APPS_PER_PAGE,
+    totalPages,
     setCurrentPage,
     searchQuery,
     isAnySettingsOpen,
@@ -298,52 +322,139 @@ function App() {
     setAppContextMenu,
   });
 
-  // Merge apps and folders into items array, using tagAssignments as single source of truth
+  // Custom tags live in the backend (scans can add some for new Finder tags)
+  async function loadCustomTags() {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      const tags = await invoke<{ key: string; label: string; icon_name: string }[]>(
+        "get_custom_tags",
+      );
+      setCustomTags(tags.map((tag) => ({ key: tag.key, label: tag.label, iconName: tag.icon_name })));
+    } catch (err) {
+      console.error("Failed to load custom tags:", err);
+    }
+  }
+
+  // Hand the tags kept in localStorage by older versions to the backend (it only
+  // imports them once), then load the custom tags from there
   useEffect(() => {
-    if (apps.length > 0) {
-      // Initialize tagAssignments for new apps with their auto-tags from Rust
-      const newAssignments: Record<string, string[]> = {};
-      let hasNewAssignments = false;
+    async function loadTags() {
+      const { invoke } = await import("@tauri-apps/api/core");
+      try {
+        const savedAssignments = localStorage.getItem("launchpad-tag-assignments");
+        const savedCustomTags = localStorage.getItem("launchpad-custom-tags");
+        await invoke("import_legacy_tags", {
+          assignments: savedAssignments ? JSON.parse(savedAssignments) : {},
+          customTags: savedCustomTags ? JSON.parse(savedCustomTags) : [],
+        });
+      } catch (err) {
+        console.error("Failed to import saved tags:", err);
+      }
 
-      apps.forEach((app) => {
-        // Check if key EXISTS (not just truthy) - empty array means user removed all tags
-        if (!(app.bundle_id in tagAssignments)) {
-          // New app - initialize with auto-tags from Rust
-          newAssignments[app.bundle_id] = app.tags || [];
-          hasNewAssignments = true;
-        }
-      });
+      await loadCustomTags();
+    }
+    loadTags();
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
 
-      // Persist new app tags to localStorage
-      if (hasNewAssignments) {
-        const updatedAssignments = { ...tagAssignments, ...newAssignments };
-        setTagAssignments(updatedAssignments);
-        localStorage.setItem("launchpad-tag-assignments", JSON.stringify(updatedAssignments));
+  // Hand the settings kept in localStorage by older versions to the backend, then load
+  // them from there (the backend applies the hot corner and shortcut itself). The UI
+  // language (the backend sorts names by it) and the settings translated into it are
+  // saved again whenever it changes.
+  useEffect(() => {
+    async function saveLocalizedSettings() {
+      const current = backendSettingsRef.current;
+      const language = i18n.resolvedLanguage ?? i18n.language;
+      const newAppsFolderName = i18n.t("folder.newAppsName");
+      if (
+        !current ||
+        (current.language === language && current.new_apps_folder_name === newAppsFolderName)
+      ) {
+        return;
       }
 
-      // Apply tags from tagAssignments (single source of truth)
-      const effectiveAssignments = hasNewAssignments
-        ? { ...tagAssignments, ...newAssignments }
-        : tagAssignments;
+      const { invoke } = await import("@tauri-apps/api/core");
+      try {
+        backendSettingsRef.current = await invoke<BackendSettings>("update_settings", {
+          settings: { ...current, language, new_apps_folder_name: newAppsFolderName },
+        });
+      } catch (err) {
+        console.error("Failed to save localized settings:", err);
+      }
+    }
+
+    async function loadSettings() {
+      const { invoke } = await import("@tauri-apps/api/core");
+      const saved = localStorage.getItem("launchpad-grid-settings");
+      if (saved) {
+        try {
+          const language = localStorage.getItem("launchpad-language");
+          await invoke("import_legacy_settings", {
+            settings: { ...JSON.parse(saved), ...(language ? { language } : {}) },
+          });
+          localStorage.removeItem("launchpad-grid-settings");
+        } catch (err) {
+          console.error("Failed to import saved settings:", err);
+        }
+      }
 
-      const appsWithTags = apps.map((app) => ({
-        ...app,
-        tags: effectiveAssignments[app.bundle_id] ?? [],
-      }));
+      try {
+        const settings = await invoke<BackendSettings>("get_settings");
+        backendSettingsRef.current = settings;
+        setGridSettings(toGridSettings(settings));
+      } catch (err) {
+        console.error("Failed to load settings:", err);
+      }
+      await saveLocalizedSettings();
+      await importLayout();
+    }
 
-      // Auto-create System and Utilities folders if they don't exist
-      const updatedFolders = createSystemFolders(appsWithTags, folders);
+    // The folders and item order kept in localStorage by older versions go to the backend
+    // layout once the imported grid size is in place (the backend only imports them once)
+    async function importLayout() {
+      const savedFolders = localStorage.getItem("launchpad-folders");
+      const savedOrder = localStorage.getItem("launchpad-item-order");
+      if (!savedFolders && !savedOrder) return;
 
-      // Save updated folders if we added any
-      if (updatedFolders.length > folders.length) {
-        saveFolders(updatedFolders);
+      const { invoke } = await import("@tauri-apps/api/core");
+      try {
+        await invoke("import_legacy_layout", {
+          folders: savedFolders ? JSON.parse(savedFolders) : [],
+          itemOrder: savedOrder ? JSON.parse(savedOrder) : {},
+        });
+        localStorage.removeItem("launchpad-folders");
+        localStorage.removeItem("launchpad-item-order");
+      } catch (err) {
+        console.error("Failed to import saved layout:", err);
       }
+    }
+    loadSettings();
+
+    i18n.on("languageChanged", saveLocalizedSettings);
+    return () => i18n.off("languageChanged", saveLocalizedSettings);
+  }, [i18n]);
+
+  // Rescanned apps carry the current tags, so local changes are only needed until then
+  useEffect(() => {
+    setTagAssignments({});
+  }, [apps]);
 
-      const newItems = mergeAppsAndFolders(appsWithTags, updatedFolders);
-      setItems(newItems);
+  // Stay on an existing page when the layout loses pages
+  useEffect(() => {
+    if (currentPage > 0 && currentPage >= totalPages) {
+      setCurrentPage(Math.max(0, totalPages - 1));
     }
-    // eslint-disable-next-line react-hooks/exhaustive-deps
-  }, [apps, folders, tagAssignments]);
+  }, [currentPage, totalPages]);
+
+  // Go to the page of a newly created folder once the layout has it
+  useEffect(() => {
+    if (!revealFolderId) return;
+    const page = pages.findIndex((p) => p.some((item) => isFolder(item) && item.id === revealFolderId));
+    if (page !== -1) {
+      setCurrentPage(page);
+      setRevealFolderId(null);
+    }
+  }, [pages, revealFolderId]);
 
   // Fetch and apply system accent color
   useEffect(() => {
@@ -509,6 +620,7 @@ function App() {
     let unlistenHotCorner: (() => void) | undefined;
     let unlistenShortcut: (() => void) | undefined;
     let unlistenAboutDialog: (() => void) | undefined;
+    let unlistenSettings: (() => void) | undefined;
 
     async function setupListeners() {
       const { getCurrentWindow } = await import("@tauri-apps/api/window");
@@ -541,14 +653,11 @@ function App() {
         setShowAboutDialog(true);
       });
 
-      // Register user's saved shortcut on startup
-      try {
-        await invoke("register_global_shortcut", {
-          shortcut: gridSettings.globalShortcut,
-        });
-      } catch (err) {
-        console.error("Failed to register saved shortcut on startup:", err);
-      }
+      // Settings saved elsewhere (e.g. a profile switch changing the grid)
+      unlistenSettings = await listen<BackendSettings>("settings-changed", ({ payload }) => {
+        backendSettingsRef.current = payload;
+        setGridSettings(toGridSettings(payload));
+      });
     }
 
     setupListeners();
@@ -557,37 +666,8 @@ function App() {
       if (unlistenHotCorner) unlistenHotCorner();
       if (unlistenShortcut) unlistenShortcut();
       if (unlistenAboutDialog) unlistenAboutDialog();
+      if (unlistenSettings) unlistenSettings();
     };
-  }, [gridSettings.globalShortcut]);
-
-  // Apply hot corner settings on startup (runs once on mount)
-  // Uses a ref to capture initial settings to avoid dependency warnings
-  const hotCornerInitializedRef = useRef(false);
-  useEffect(() => {
-    if (hotCornerInitializedRef.current) return;
-    hotCornerInitializedRef.current = true;
-
-    async function applyHotCornerOnStartup() {
-      const { invoke } = await import("@tauri-apps/api/core");
-      // Read from localStorage directly to get the initial saved settings
-      const saved = localStorage.getItem("launchpad-grid-settings");
-      if (!saved) return;
-
-      try {
-        const settings = JSON.parse(saved);
-        if (settings.hotCornerEnabled) {
-          await invoke("enable_hot_corner", {
-            corner: settings.hotCorner || "top-left",
-            threshold: settings.hotCornerThreshold || 10,
-            debounceMs: settings.hotCornerDebounce || 300,
-          });
-        }
-      } catch (err) {
-        console.error("Failed to apply hot corner settings on startup:", err);
-      }
-    }
-
-    applyHotCornerOnStartup();
   }, []);
 
   // Context menu handler
@@ -604,133 +684,141 @@ function App() {
     setContextMenu(null);
   }
 
-  // Save grid settings
+  // Save grid settings; the backend validates and applies them
   async function saveGridSettings() {
-    localStorage.setItem("launchpad-grid-settings", JSON.stringify(gridSettings));
+    const current = backendSettingsRef.current;
+    if (current) {
+      const { invoke } = await import("@tauri-apps/api/core");
+      try {
+        const settings = await invoke<BackendSettings>("update_settings", {
+          settings: toBackendSettings(gridSettings, current),
+        });
+        backendSettingsRef.current = settings;
+        setGridSettings(toGridSettings(settings));
+      } catch (err) {
+        console.error("Failed to save settings:", err);
+        setGridSettings(toGridSettings(current));
+      }
+    }
 
-    const { invoke } = await import("@tauri-apps/api/core");
+    closeAllSettings();
+  }
 
-    // Apply hot corner settings
+  // Sort all items alphabetically (the backend sorts by the UI language)
+  async function sortAlphabetically() {
+    const { invoke } = await import("@tauri-apps/api/core");
     try {
-      if (gridSettings.hotCornerEnabled) {
-        await invoke("enable_hot_corner", {
-          corner: gridSettings.hotCorner,
-          threshold: gridSettings.hotCornerThreshold,
-          debounceMs: gridSettings.hotCornerDebounce,
-        });
-      } else {
-        await invoke("disable_hot_corner");
-      }
+      await invoke("sort_layout", { scope: { type: "all" }, key: "name" });
+      setCurrentPage(0);
     } catch (err) {
-      console.error("Failed to update hot corner settings:", err);
+      console.error("Failed to sort apps:", err);
     }
+  }
 
-    // Register global shortcut
+  // Drop all folders and positions in the backend (it snapshots first), then show its grid
+  async function resetLayout() {
+    const { invoke } = await import("@tauri-apps/api/core");
     try {
-      await invoke("register_global_shortcut", {
-        shortcut: gridSettings.globalShortcut,
-      });
+      await invoke("reset_layout");
+      setCurrentPage(0);
+      await loadLayout();
     } catch (err) {
-      console.error("Failed to register global shortcut:", err);
+      console.error("Failed to reset layout:", err);
     }
-
-    closeAllSettings();
   }
 
-  // Sort all items alphabetically
-  function sortAlphabetically() {
-    const sorted = [...items].sort((a, b) =>
-      a.name.toLowerCase().localeCompare(b.name.toLowerCase())
-    );
-    setItems(sorted);
-    saveItemOrder(sorted);
-    setCurrentPage(0);
-  }
+  // Backend position of a slot on a page: the place of the item shown there, or the
+  // end of the page for slots after the last item
+  function layoutIndex(page: number, slot: number): number {
+    const layoutItems = layout?.pages[page]?.items ?? [];
+    const shown = pages[page]?.[slot];
+    if (!shown) return layoutItems.length;
 
-  // Tag manipulation handlers
-  function handleToggleTag(bundleId: string, tagKey: string) {
-    // Update items state
-    setItems((prevItems) =>
-      prevItems.map((item) => {
-        if (!isFolder(item)) {
-          const app = item as App;
-          if (app.bundle_id === bundleId) {
-            const tags = app.tags || [];
-            const hasTag = tags.includes(tagKey);
-            return {
-              ...app,
-              tags: hasTag
-                ? tags.filter((t) => t !== tagKey)
-                : [...tags, tagKey],
-            };
-          }
-        }
-        return item;
-      }),
-    );
+    const key = (ref: LayoutItemRef) => (ref.type === "app" ? `app:${ref.bundle_id}` : `folder:${ref.id}`);
+    const index = layoutItems.findIndex((ref) => key(ref) === key(toItemRef(shown)));
+    return index === -1 ? layoutItems.length : index;
+  }
 
-    // Persist tag assignment to localStorage (single source of truth)
-    setTagAssignments((prev) => {
-      const currentTags = prev[bundleId] || [];
-      const hasTag = currentTags.includes(tagKey);
-      const newTags = hasTag
-        ? currentTags.filter((t) => t !== tagKey)
-        : [...currentTags, tagKey];
+  // Move an item (or an app out of its folder) to a slot on a page
+  async function moveItem(item: LaunchpadItem, page: number, slot: number) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      await invoke("move_layout_item", { item: toItemRef(item), page, index: layoutIndex(page, slot) });
+    } catch (err) {
+      console.error("Failed to move item:", err);
+    }
+  }
 
-      // Always store the result, even if empty - empty array means "user removed all tags"
-      const updated = { ...prev, [bundleId]: newTags };
+  // Drop an item onto another: two apps make a folder, an app dropped on a folder joins it
+  async function mergeItems(source: LaunchpadItem, target: LaunchpadItem) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      await invoke("merge_layout_items", { source: toItemRef(source), target: toItemRef(target) });
+    } catch (err) {
+      console.error("Failed to merge items:", err);
+    }
+  }
 
-      localStorage.setItem("launchpad-tag-assignments", JSON.stringify(updated));
-      return updated;
-    });
+  // Tag manipulation handlers
+  async function handleToggleTag(bundleId: string, tagKey: string) {
+    const app = apps.find((a) => a.bundle_id === bundleId);
+    const currentTags = tagAssignments[bundleId] ?? app?.tags ?? [];
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      const tags = await invoke<string[]>(
+        currentTags.includes(tagKey) ? "unassign_tag" : "assign_tag",
+        { bundleId, tagKey },
+      );
+      setTagAssignments((prev) => ({ ...prev, [bundleId]: tags }));
+    } catch (err) {
+      console.error("Failed to change app tags:", err);
+    }
   }
 
   function handleCreateTag() {
     setShowCreateTagModal(true);
   }
 
-  function handleTagCreated(tagData: { key: string; label: string; iconName: string }) {
-    // Check if tag with same key already exists - update it instead of duplicating
-    const existingIndex = customTags.findIndex(t => t.key === tagData.key);
-    let updatedCustomTags: typeof customTags;
-
-    if (existingIndex >= 0) {
-      // Update existing tag (e.g., if user re-creates with different icon)
-      updatedCustomTags = [...customTags];
-      updatedCustomTags[existingIndex] = tagData;
-    } else {
-      // Add new tag
-      updatedCustomTags = [...customTags, tagData];
+  async function handleTagCreated(tagData: { label: string; iconName: string }) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      // The backend derives the key; re-creating an existing tag updates it
+      const tag = await invoke<{ key: string; label: string; icon_name: string }>("create_tag", {
+        label: tagData.label,
+        iconName: tagData.iconName,
+      });
+      const created = { key: tag.key, label: tag.label, iconName: tag.icon_name };
+      setCustomTags((prev) =>
+        prev.some((t) => t.key === created.key)
+          ? prev.map((t) => (t.key === created.key ? created : t))
+          : [...prev, created],
+      );
+    } catch (err) {
+      console.error("Failed to create tag:", err);
     }
-
-    setCustomTags(updatedCustomTags);
-    localStorage.setItem("launchpad-custom-tags", JSON.stringify(updatedCustomTags));
   }
 
-  function handleDeleteTag(tagKey: string) {
-    // Remove tag from custom tags
-    const updatedCustomTags = customTags.filter((t) => t.key !== tagKey);
-    setCustomTags(updatedCustomTags);
-    localStorage.setItem(
-      "launchpad-custom-tags",
-      JSON.stringify(updatedCustomTags),
-    );
+  async function handleDeleteTag(tagKey: string) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      await invoke("delete_tag", { key: tagKey });
+    } catch (err) {
+      console.error("Failed to delete tag:", err);
+      return;
+    }
+    setCustomTags((prev) => prev.filter((t) => t.key !== tagKey));
 
     // Remove tag from all apps
-    setItems((prevItems) =>
-      prevItems.map((item) => {
-        if (!isFolder(item)) {
-          const app = item as App;
-          if (app.tags?.includes(tagKey)) {
-            return {
-              ...app,
-              tags: app.tags.filter((t) => t !== tagKey),
-            };
-          }
+    setTagAssignments((prev) => {
+      const updated = { ...prev };
+      apps.forEach((app) => {
+        const tags = prev[app.bundle_id] ?? app.tags ?? [];
+        if (tags.includes(tagKey)) {
+          updated[app.bundle_id] = tags.filter((t) => t !== tagKey);
         }
-        return item;
-      }),
-    );
+      });
+      return updated;
+    });
 
     // Clear selected tag if it was the deleted one
     if (selectedTag === tagKey) {
@@ -738,6 +826,21 @@ function App() {
     }
   }
 
+  // Drop all custom tags and manual tag changes, then reload apps to get fresh auto-tags
+  async function resetTags() {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      await invoke("reset_tags");
+    } catch (err) {
+      console.error("Failed to reset tags:", err);
+      return;
+    }
+    setTagAssignments({});
+    setSelectedTag(null);
+    await loadApps();
+    await loadCustomTags();
+  }
+
   // Handle app right-click
   function handleAppContextMenu(e: React.MouseEvent, app: any) {
     e.preventDefault();
@@ -750,7 +853,24 @@ function App() {
       appName: app.name,
       appPath: app.path,
       bundleId: app.bundle_id,
+      launchPresets: [],
     });
+    loadLaunchPresets(app.bundle_id);
+  }
+
+  // Saved launch presets show up as extra "Open" entries in the app context menu
+  async function loadLaunchPresets(bundleId: string) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    try {
+      const presets = await invoke<{ name: string }[]>("get_launch_presets", { bundleId });
+      setAppContextMenu((menu) =>
+        menu && menu.bundleId === bundleId
+          ? { ...menu, launchPresets: presets.map((preset) => preset.name) }
+          : menu,
+      );
+    } catch (err) {
+      console.error("Failed to load launch presets:", err);
+    }
   }
 
   // App context menu actions
@@ -926,6 +1046,17 @@ function App() {
         </>
       )}
 
+      {launchError && (
+        <div className="launch-error" role="alert" onClick={dismissLaunchError}>
+          <p>
+            {t(`launchError.${launchError.kind}`, {
+              name: launchError.app_path.split("/").pop()?.replace(/\.app$/, ""),
+            })}
+          </p>
+          <p className="launch-error-details">{launchError.message}</p>
+        </div>
+      )}
+
       {!loading && !error && currentItems.length === 0 && searchQuery && (
         <div className="no-results">
           <p>{t("search.noResults", { query: searchQuery })}</p>
@@ -956,32 +1087,12 @@ function App() {
       {contextMenu && (
         <ContextMenu
           position={contextMenu}
-          onCreateFolder={() => {
-            const currentItemCount = items.length;
-            createFolder();
+          onCreateFolder={async () => {
             setContextMenu(null);
-
-            // Wait for items to update, then navigate to last page
-            setTimeout(() => {
-              // New folder will be at the end after merge
-              const newItemCount = currentItemCount + 1;
-              const totalPages = Math.ceil(newItemCount / APPS_PER_PAGE);
-              const lastPage = Math.max(0, totalPages - 1);
-
-              console.log('Create folder navigation:', {
-                currentItemCount,
-                newItemCount,
-                APPS_PER_PAGE,
-                totalPages,
-                lastPage,
-                currentPage,
-                willNavigate: lastPage !== currentPage
-              });
-
-              if (lastPage !== currentPage) {
-                setCurrentPage(lastPage);
-              }
-            }, 150);
+            const folder = await createFolder(t("folder.newFolderName"));
+            if (folder) {
+              setRevealFolderId(folder.id);
+            }
           }}
           onSortAlphabetically={() => {
             sortAlphabetically();
@@ -990,19 +1101,8 @@ function App() {
           onEditApps={toggleEditMode}
           onResetTags={() => {
             if (confirm("Reset all tags? This will clear all tag assignments and let you test auto-tagging.")) {
-              // Clear custom tags
-              setCustomTags([]);
-              localStorage.removeItem("launchpad-custom-tags");
-              // Clear tag assignments
-              setTagAssignments({});
-              localStorage.removeItem("launchpad-tag-assignments");
-
-              // Clear folders to remove old cached tags
-              localStorage.removeItem("launchpad-folders");
-              setFolders([]);
-
-              // Reload apps to get fresh auto-tags from Rust
-              loadApps();
+              // Folders go too so no old cached tags are left in them
+              resetTags().then(resetLayout);
             }
             setContextMenu(null);
           }}
@@ -1111,7 +1211,9 @@ function App() {
             appName={appContextMenu.appName}
             appTags={appTags}
             availableTags={availableTags}
+            launchPresets={appContextMenu.launchPresets}
             onOpen={() => launchApp(appContextMenu.appPath)}
+            onOpenWithPreset={(preset) => launchApp(appContextMenu.appPath, preset)}
             onRevealInFinder={() => revealInFinder(appContextMenu.appPath)}
             onMoveToTrash={() => confirmMoveToTrash(appContextMenu.appName, appContextMenu.appPath)}
             onToggleTag={(tagKey) => handleToggleTag(appContextMenu.bundleId, tagKey)}
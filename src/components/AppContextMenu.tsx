@@ -10,7 +10,9 @@ interface AppContextMenuProps {
     appName: string;
     appTags: string[];
     availableTags: Tag[];
+    launchPresets: string[];
     onOpen: () => void;
+    onOpenWithPreset: (preset: string) => void;
     onRevealInFinder: () => void;
     onMoveToTrash: () => void;
     onToggleTag: (tagKey: string) => void;
@@ -28,7 +30,9 @@ export function AppContextMenu({
                                    appName: _appName,
                                    appTags,
                                    availableTags,
+                                   launchPresets,
                                    onOpen,
+                                   onOpenWithPreset,
                                    onRevealInFinder,
                                    onMoveToTrash,
                                    onToggleTag,
@@ -56,6 +60,21 @@ export function AppContextMenu({
             >
                 {t('appContextMenu.open')}
             </Button>
+            {launchPresets.map((preset) => (
+                <Button
+                    key={preset}
+                    onClick={() => {
+                        onOpenWithPreset(preset)
+                        onClose()
+                    }}
+                    align="left"
+                    variant="ghost"
+                    size="sm"
+                    className={menuItemClass}
+                >
+                    {t('appContextMenu.openWithPreset', {name: preset})}
+                </Button>
+            ))}
             <Button
                 onClick={() => {
                     onRevealInFinder()
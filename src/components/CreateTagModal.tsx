@@ -1685,7 +1685,7 @@ AVAILABLE_ICONS.forEach(({ name, component }) => {
 
 interface CreateTagModalProps {
   onClose: () => void;
-  onCreateTag: (tagData: { key: string; label: string; iconName: string }) => void;
+  onCreateTag: (tagData: { label: string; iconName: string }) => void;
 }
 
 export function CreateTagModal({ onClose, onCreateTag }: CreateTagModalProps) {
@@ -1703,12 +1703,7 @@ export function CreateTagModal({ onClose, onCreateTag }: CreateTagModalProps) {
   const handleSubmit = (e: React.FormEvent) => {
     e.preventDefault();
     if (tagName.trim()) {
-      const key = tagName
-        .toLowerCase()
-        .replace(/\s+/g, "-")
-        .replace(/[^a-z0-9-]/g, "");
       onCreateTag({
-        key: `custom-${key}`,
         label: tagName.trim(),
         iconName: selectedIcon,
       });
@@ -1,9 +1,8 @@
-import { useState, useEffect, useCallback, useRef } from "react";
+import { useState, useEffect, useCallback, useRef, useMemo } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
-import type { App, Folder, LaunchpadItem } from "../types";
-import { isFolder } from "../types";
+import type { App, LaunchError, LaunchpadItem, Layout } from "../types";
 
 /** Icon update payload from backend */
 interface IconUpdate {
@@ -12,19 +11,62 @@ interface IconUpdate {
 }
 
 /**
- * Hook that manages app loading, launching, and item ordering
+ * Resolve the pages of the backend layout against the scanned apps
+ */
+function buildPages(apps: App[], layout: Layout | null): LaunchpadItem[][] {
+  if (apps.length === 0 || !layout) return [];
+
+  const appMap = new Map(apps.map((app) => [app.bundle_id, app]));
+  const folderMap = new Map(layout.folders.map((folder) => [folder.id, folder]));
+
+  return layout.pages.map((page) =>
+    page.items.flatMap((item): LaunchpadItem[] => {
+      if (item.type === "app") {
+        const app = appMap.get(item.bundle_id);
+        return app ? [app] : [];
+      }
+
+      const folder = folderMap.get(item.id);
+      if (!folder) return [];
+      const folderApps = folder.apps.flatMap((bundleId) => {
+        const app = appMap.get(bundleId);
+        return app ? [app] : [];
+      });
+      return [{ id: folder.id, name: folder.name, apps: folderApps }];
+    }),
+  );
+}
+
+/**
+ * Hook that manages app loading and launching, and the layout the backend keeps them in
  * Uses progressive loading for fast startup
  */
 export function useAppManagement() {
   const [apps, setApps] = useState<App[]>([]);
-  const [items, setItems] = useState<LaunchpadItem[]>([]);
+  const [layout, setLayout] = useState<Layout | null>(null);
   const [loading, setLoading] = useState(true);
   const [iconsLoading, setIconsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [launchError, setLaunchError] = useState<LaunchError | null>(null);
 
   // Keep a ref to apps for icon updates without causing re-renders
   const appsRef = useRef<App[]>([]);
 
+  // Pages of the grid as the backend layout arranges them (hidden apps are left out)
+  const pages = useMemo(() => buildPages(apps, layout), [apps, layout]);
+  const items = useMemo(() => pages.flat(), [pages]);
+
+  /**
+   * Load the layout from the backend
+   */
+  async function loadLayout() {
+    try {
+      setLayout(await invoke<Layout>("get_layout"));
+    } catch (err) {
+      console.error("Failed to load layout:", err);
+    }
+  }
+
   /**
    * Load installed applications using progressive loading
    * 1. First, get apps quickly without icons
@@ -36,7 +78,9 @@ export function useAppManagement() {
       setIconsLoading(true);
 
       // Phase 1: Get apps quickly without icons
+      // (the scan also brings the layout up to date with what is installed)
       const installedApps = await invoke<App[]>("get_installed_apps_fast");
+      await loadLayout();
       appsRef.current = installedApps;
       setApps(installedApps);
       setLoading(false); // Apps are ready to display!
@@ -83,6 +127,18 @@ export function useAppManagement() {
     let unlistenIcons: UnlistenFn | null = null;
     let unlistenComplete: UnlistenFn | null = null;
     let unlistenAppsChanged: UnlistenFn | null = null;
+    let unlistenLayoutChanged: UnlistenFn | null = null;
+    let unlistenHiddenChanged: UnlistenFn | null = null;
+
+    function reloadApps() {
+      // Prevent concurrent reloads
+      if (!isReloadingRef.current) {
+        isReloadingRef.current = true;
+        loadApps().finally(() => {
+          isReloadingRef.current = false;
+        });
+      }
+    }
 
     async function setupListeners() {
       // Listen for batched icon updates
@@ -98,13 +154,17 @@ export function useAppManagement() {
       // Listen for app changes (new apps installed, apps removed)
       unlistenAppsChanged = await listen("apps-changed", () => {
         console.log("[useAppManagement] Apps changed, reloading...");
-        // Prevent concurrent reloads
-        if (!isReloadingRef.current) {
-          isReloadingRef.current = true;
-          loadApps().finally(() => {
-            isReloadingRef.current = false;
-          });
-        }
+        reloadApps();
+      });
+
+      // Hidden apps are left out of the scan, so showing or hiding one needs a reload
+      unlistenHiddenChanged = await listen("hidden-apps-changed", () => {
+        reloadApps();
+      });
+
+      // Listen for layout changes (the payload still lists hidden apps, so reload instead)
+      unlistenLayoutChanged = await listen("layout-changed", () => {
+        loadLayout();
       });
     }
 
@@ -114,110 +174,35 @@ export function useAppManagement() {
       if (unlistenIcons) unlistenIcons();
       if (unlistenComplete) unlistenComplete();
       if (unlistenAppsChanged) unlistenAppsChanged();
+      if (unlistenLayoutChanged) unlistenLayoutChanged();
+      if (unlistenHiddenChanged) unlistenHiddenChanged();
     };
   }, [handleIconUpdates]);
 
   /**
-   * Launch an application and minimize the window
+   * Launch an application (with one of its saved launch presets if given) and minimize the window.
+   * If the launch fails the window stays open and launchError is set.
    */
-  async function launchApp(appPath: string) {
+  async function launchApp(appPath: string, preset?: string) {
     try {
-      await invoke("launch_app", { appPath });
+      setLaunchError(null);
+      if (preset) {
+        await invoke("launch_app_with_options", { appPath, preset });
+      } else {
+        await invoke("launch_app", { appPath });
+      }
       // Minimize to dock after launching
       await getCurrentWindow().minimize();
     } catch (err) {
       console.error("Failed to launch app:", err);
+      setLaunchError(
+        typeof err === "object" && err !== null && "kind" in err
+          ? (err as LaunchError)
+          : { kind: "unknown", app_path: appPath, message: String(err) },
+      );
     }
   }
 
-  /**
-   * Save item order to localStorage
-   */
-  function saveItemOrder(newItems: LaunchpadItem[]) {
-    const orderMap: Record<string, number> = {};
-    newItems.forEach((item, index) => {
-      const id = isFolder(item) ? item.id : item.bundle_id;
-      orderMap[id] = index;
-    });
-    localStorage.setItem("launchpad-item-order", JSON.stringify(orderMap));
-  }
-
-  /**
-   * Merge apps and folders into a unified items array
-   * This function syncs folder apps with fresh app data to ensure tags are up-to-date
-   */
-  function mergeAppsAndFolders(apps: App[], folders: Folder[]): LaunchpadItem[] {
-    if (apps.length === 0) return [];
-
-    // Create a map of bundle_id to fresh app data for quick lookup
-    const appMap = new Map(apps.map((app) => [app.bundle_id, app]));
-
-    // Update folders with fresh app data (to sync tags and other properties)
-    const updatedFolders = folders.map((folder) => ({
-      ...folder,
-      apps: folder.apps
-        .map((folderApp) => {
-          // Use fresh app data if available, otherwise keep existing
-          const freshApp = appMap.get(folderApp.bundle_id);
-          return freshApp || folderApp;
-        })
-        .filter((app) => appMap.has(app.bundle_id)), // Remove apps that no longer exist
-    }));
-
-    const folderAppIds = new Set(updatedFolders.flatMap((f) => f.apps.map((a) => a.bundle_id)));
-    const standaloneApps = apps.filter((app) => !folderAppIds.has(app.bundle_id));
-    const newItems: LaunchpadItem[] = [...standaloneApps, ...updatedFolders];
-
-    // Load custom item order from localStorage
-    const savedOrder = localStorage.getItem("launchpad-item-order");
-    if (savedOrder) {
-      try {
-        const orderMap = JSON.parse(savedOrder) as Record<string, number>;
-        newItems.sort((a, b) => {
-          const idA = isFolder(a) ? a.id : a.bundle_id;
-          const idB = isFolder(b) ? b.id : b.bundle_id;
-          const posA = orderMap[idA] ?? 9999;
-          const posB = orderMap[idB] ?? 9999;
-          return posA - posB;
-        });
-      } catch (err) {
-        console.error("Failed to load item order:", err);
-      }
-    }
-
-    return newItems;
-  }
-
-  /**
-   * Auto-create system folders on initial load
-   */
-  function createSystemFolders(apps: App[], folders: Folder[]): Folder[] {
-    const systemApps = apps.filter((app) => app.source_folder === "System");
-    const utilityApps = apps.filter((app) => app.source_folder === "Utilities");
-
-    const updatedFolders = [...folders];
-
-    // Create System folder if we have system apps and folder doesn't exist
-    if (systemApps.length > 0 && !folders.find((f) => f.id === "folder-system")) {
-      updatedFolders.push({
-        id: "folder-system",
-        name: "System",
-        apps: systemApps,
-      });
-    }
-
-    // Create Utilities folder if we have utility apps and folder doesn't exist
-    if (utilityApps.length > 0 && !folders.find((f) => f.id === "folder-utilities")) {
-      updatedFolders.push({
-        id: "folder-utilities",
-        name: "Utilities",
-        apps: utilityApps,
-      });
-    }
-
-    return updatedFolders;
-  }
-
   // Load apps and show window on mount
   useEffect(() => {
     loadApps();
@@ -232,16 +217,17 @@ export function useAppManagement() {
 
   return {
     apps,
+    pages,
     items,
+    layout,
     loading,
     iconsLoading,
     error,
     setApps,
-    setItems,
     loadApps,
+    loadLayout,
     launchApp,
-    saveItemOrder,
-    mergeAppsAndFolders,
-    createSystemFolders,
+    launchError,
+    dismissLaunchError: () => setLaunchError(null),
   };
 }
@@ -5,19 +5,24 @@ import { isFolder } from "../types";
 
 interface UseDragAndDropProps {
   items: LaunchpadItem[];
-  setItems: (items: LaunchpadItem[]) => void;
-  folders: Folder[];
-  setFolders: (folders: Folder[]) => void;
   openFolder: Folder | null;
-  setOpenFolder: (folder: Folder | null) => void;
   currentPage: number;
   setCurrentPage: (page: number) => void;
-  appsPerPage: number;
-  filteredItems: LaunchpadItem[];
+  pageStarts: number[]; // Index in items of the first item of each page
+  totalPages: number;
   isFiltering: boolean; // True when tag filter or search is active
   gridSettings: { rows: number; cols: number };
-  saveItemOrder: (items: LaunchpadItem[]) => void;
-  saveFolders: (folders: Folder[]) => void;
+  onMoveItem: (item: LaunchpadItem, page: number, slot: number) => void; // Move to a page slot
+  onMergeItems: (source: LaunchpadItem, target: LaunchpadItem) => void; // Drop onto another item
+}
+
+/**
+ * Page of an index in the flat items list
+ */
+function pageOf(pageStarts: number[], index: number): number {
+  let page = 0;
+  while (page + 1 < pageStarts.length && pageStarts[page + 1] <= index) page++;
+  return page;
 }
 
 /**
@@ -26,19 +31,15 @@ interface UseDragAndDropProps {
  */
 export function useDragAndDrop({
   items,
-  setItems,
-  folders,
-  setFolders,
   openFolder,
-  setOpenFolder,
   currentPage,
   setCurrentPage,
-  appsPerPage,
-  filteredItems,
+  pageStarts,
+  totalPages,
   isFiltering,
   gridSettings,
-  saveItemOrder,
-  saveFolders,
+  onMoveItem,
+  onMergeItems,
 }: UseDragAndDropProps) {
   const [draggedIndex, setDraggedIndex] = useState<number | null>(null);
   const [draggedItem, setDraggedItem] = useState<LaunchpadItem | null>(null);
@@ -59,24 +60,26 @@ export function useDragAndDrop({
   const mouseDownItemRef = useRef(mouseDownItem);
   const previewTargetIndexRef = useRef(previewTargetIndex);
   const itemsRef = useRef(items);
-  const foldersRef = useRef(folders);
   const openFolderRef = useRef(openFolder);
   const currentPageRef = useRef(currentPage);
   const isFilteringRef = useRef(isFiltering);
   const gridSettingsRef = useRef(gridSettings);
-  const appsPerPageRef = useRef(appsPerPage);
+  const pageStartsRef = useRef(pageStarts);
+  const totalPagesRef = useRef(totalPages);
+  const onMoveItemRef = useRef(onMoveItem);
 
   // Ref for handleDrop function (defined later)
   const handleDropRef = useRef<((e: React.DragEvent | null, targetIndex: number, targetItem: LaunchpadItem) => void) | null>(null);
 
   // Keep refs in sync with props (these change externally)
   useEffect(() => { itemsRef.current = items; }, [items]);
-  useEffect(() => { foldersRef.current = folders; }, [folders]);
   useEffect(() => { openFolderRef.current = openFolder; }, [openFolder]);
   useEffect(() => { currentPageRef.current = currentPage; }, [currentPage]);
   useEffect(() => { isFilteringRef.current = isFiltering; }, [isFiltering]);
   useEffect(() => { gridSettingsRef.current = gridSettings; }, [gridSettings]);
-  useEffect(() => { appsPerPageRef.current = appsPerPage; }, [appsPerPage]);
+  useEffect(() => { pageStartsRef.current = pageStarts; }, [pageStarts]);
+  useEffect(() => { totalPagesRef.current = totalPages; }, [totalPages]);
+  onMoveItemRef.current = onMoveItem;
 
   // Wrapper setters that update refs SYNCHRONOUSLY (avoids race conditions)
   const setIsDraggingWithRef = (value: boolean) => {
@@ -140,111 +143,51 @@ export function useDragAndDrop({
 
     // Safety check
     if (actualDraggedIndex === -1 || actualTargetIndex === -1) {
-      setIsDragging(false);
-      setDraggedIndex(null);
-      setDraggedItem(null);
+      setIsDraggingWithRef(false);
+      setDraggedIndexWithRef(null);
+      setDraggedItemWithRef(null);
       setDragOverIndex(null);
-      setPreviewTargetIndex(null);
+      setPreviewTargetIndexWithRef(null);
       return;
     }
 
     // Same position, no change needed
     if (actualDraggedIndex === actualTargetIndex) {
-      setIsDragging(false);
-      setDraggedIndex(null);
-      setDraggedItem(null);
+      setIsDraggingWithRef(false);
+      setDraggedIndexWithRef(null);
+      setDraggedItemWithRef(null);
       setDragOverIndex(null);
-      setPreviewTargetIndex(null);
+      setPreviewTargetIndexWithRef(null);
       return;
     }
 
+    // Drag state is cleared through the refs so the window mouseup handler doesn't drop again
     const draggedIsApp = !isFolder(draggedItem);
-    const targetIsApp = !isFolder(targetItem);
-
-    // If dragging an app onto another app, create a folder
-    if (draggedIsApp && targetIsApp) {
-      const newFolder: Folder = {
-        id: `folder-${Date.now()}`,
-        name: "Folder",
-        apps: [targetItem, draggedItem],
-      };
-
-      const newItems = [...items];
-      // Remove in descending order to maintain indices
-      const indicesToRemove = [actualDraggedIndex, actualTargetIndex].sort((a, b) => b - a);
-      indicesToRemove.forEach((idx) => newItems.splice(idx, 1));
-
-      // Insert at the lower index position
-      const insertPos = Math.min(actualDraggedIndex, actualTargetIndex);
-      newItems.splice(insertPos, 0, newFolder);
-
-      // Calculate the page where the folder will appear
-      const folderPage = Math.floor(insertPos / appsPerPage);
-
-      console.log('Folder creation:', {
-        actualDraggedIndex,
-        actualTargetIndex,
-        insertPos,
-        appsPerPage,
-        folderPage,
-        currentPage,
-        totalItems: newItems.length
-      });
-
-      // Update items first, then navigate after a brief delay to ensure React has updated
-      setItems(newItems);
-      saveFolders([...folders, newFolder]);
-      saveItemOrder(newItems);
-
-      // Navigate to the folder's page after state updates
-      if (folderPage !== currentPage) {
-        setTimeout(() => {
-          console.log('Navigating to page:', folderPage);
-          setCurrentPage(folderPage);
-        }, 50);
-      }
-      setIsDragging(false);
-      setDraggedIndex(null);
-      setDraggedItem(null);
-      setDragOverIndex(null);
-      setPreviewTargetIndex(null);
-      return;
-    }
+    const targetPage = pageOf(pageStarts, actualTargetIndex);
+
+    // Dragging an app onto another app creates a folder, onto a folder adds it to the folder
+    if (draggedIsApp) {
+      onMergeItems(draggedItem, targetItem);
 
-    // If dragging an app onto a folder, add to folder
-    if (draggedIsApp && isFolder(targetItem)) {
-      const folder = targetItem;
-      const updatedFolder = {
-        ...folder,
-        apps: [...folder.apps, draggedItem],
-      };
-
-      const newFolders = folders.map((f) => (f.id === folder.id ? updatedFolder : f));
-      const newItems = items.filter((_, i) => i !== actualDraggedIndex);
-
-      setItems(newItems);
-      saveFolders(newFolders);
-      saveItemOrder(newItems);
-      setIsDragging(false);
-      setDraggedIndex(null);
-      setDraggedItem(null);
+      // The folder takes the target's place; follow it if that is on another page
+      if (targetPage !== currentPage) {
+        setCurrentPage(targetPage);
+      }
+      setIsDraggingWithRef(false);
+      setDraggedIndexWithRef(null);
+      setDraggedItemWithRef(null);
       setDragOverIndex(null);
-      setPreviewTargetIndex(null);
+      setPreviewTargetIndexWithRef(null);
       return;
     }
 
-    // Regular reordering - move item from actualDraggedIndex to actualTargetIndex
-    const newItems = [...items];
-    const [removed] = newItems.splice(actualDraggedIndex, 1);
-    newItems.splice(actualTargetIndex, 0, removed);
-
-    setItems(newItems);
-    saveItemOrder(newItems);
-    setIsDragging(false);
-    setDraggedIndex(null);
-    setDraggedItem(null);
+    // Regular reordering - move the dragged item to the target's place
+    onMoveItem(draggedItem, targetPage, actualTargetIndex - pageStarts[targetPage]);
+    setIsDraggingWithRef(false);
+    setDraggedIndexWithRef(null);
+    setDraggedItemWithRef(null);
     setDragOverIndex(null);
-    setPreviewTargetIndex(null);
+    setPreviewTargetIndexWithRef(null);
   };
 
   // Keep handleDrop ref in sync
@@ -286,8 +229,7 @@ export function useDragAndDrop({
         // Edge detection for page switching (100px zones on left/right)
         const EDGE_ZONE = 100;
         const screenWidth = window.innerWidth;
-        const curAppsPerPage = appsPerPageRef.current;
-        const pages = Math.ceil(filteredItems.length / curAppsPerPage);
+        const pages = totalPagesRef.current;
 
         // Clear any existing timer
         if (pageSwitchTimerRef.current !== null) {
@@ -387,8 +329,11 @@ export function useDragAndDrop({
             const col = Math.max(0, Math.floor(gridX / itemWidth));
             const row = Math.max(0, Math.floor(gridY / itemHeight));
             const targetIndex = row * cols + col;
-            const currentStartIndex = curPage * curAppsPerPage;
-            const targetGlobalIndex = currentStartIndex + targetIndex;
+            const starts = pageStartsRef.current;
+            const currentStartIndex = starts[curPage] ?? 0;
+            const pageEnd = starts[curPage + 1] ?? curItems.length;
+            // Empty slots after the last item of the page count as its end
+            const targetGlobalIndex = Math.min(currentStartIndex + targetIndex, pageEnd - 1);
 
             if (
               targetGlobalIndex >= 0 &&
@@ -419,7 +364,6 @@ export function useDragAndDrop({
       const currentDraggedItem = draggedItemRef.current;
       const currentDraggedIndex = draggedIndexRef.current;
       const curItems = itemsRef.current;
-      const curFolders = foldersRef.current;
       const curOpenFolder = openFolderRef.current;
       const curPage = currentPageRef.current;
       const curPreviewTarget = previewTargetIndexRef.current;
@@ -447,17 +391,17 @@ export function useDragAndDrop({
               !isDraggingFromFolder
             ) {
               const targetItem = curItems[targetGlobalIndex];
-              const curAppsPerPage = appsPerPageRef.current;
-              const currentStartIndex = curPage * curAppsPerPage;
+              const currentStartIndex = pageStartsRef.current[curPage] ?? 0;
               const targetIndexInPage = targetGlobalIndex - currentStartIndex;
 
               handleDropRef.current?.(null, targetIndexInPage, targetItem);
             }
           }
         } else {
-          let targetGlobalIndex = curPreviewTarget;
+          const currentStartIndex = pageStartsRef.current[curPage] ?? 0;
+          let targetSlot = curPreviewTarget === null ? null : curPreviewTarget - currentStartIndex;
 
-          if (targetGlobalIndex === null) {
+          if (targetSlot === null) {
             const gridElement = elementsAtPoint.find((el) => el.classList.contains("apps-grid")) as
               | HTMLElement
               | undefined;
@@ -474,7 +418,6 @@ export function useDragAndDrop({
               const gridY = e.clientY - rect.top - paddingTop;
 
               const cols = gridSettingsRef.current.cols;
-              const curAppsPerPage = appsPerPageRef.current;
               const firstItem = gridElement.querySelector(".app-item") as HTMLElement | null;
               let itemWidth = (rect.width - paddingLeft * 2) / cols;
               let itemHeight = itemWidth;
@@ -504,35 +447,18 @@ export function useDragAndDrop({
               const col = Math.max(0, Math.floor(gridX / itemWidth));
               const row = Math.max(0, Math.floor(gridY / itemHeight));
 
-              const targetIndex = row * cols + col;
-              const currentStartIndex = curPage * curAppsPerPage;
-              targetGlobalIndex = currentStartIndex + targetIndex;
+              targetSlot = row * cols + col;
             }
           }
 
           if (
-            targetGlobalIndex !== null &&
-            targetGlobalIndex < curItems.length &&
+            targetSlot !== null &&
             currentDraggedIndex !== null &&
-            targetGlobalIndex !== currentDraggedIndex &&
+            currentStartIndex + targetSlot !== currentDraggedIndex &&
             !isDraggingFromFolder &&
             currentDraggedItem
           ) {
-            // Find actual index in items array
-            const actualDraggedIndex = curItems.findIndex((item) => {
-              const itemId = isFolder(item) ? item.id : item.bundle_id;
-              const draggedId = isFolder(currentDraggedItem) ? currentDraggedItem.id : currentDraggedItem.bundle_id;
-              return itemId === draggedId;
-            });
-
-            if (actualDraggedIndex !== -1) {
-              const newItems = [...curItems];
-              const [removed] = newItems.splice(actualDraggedIndex, 1);
-              newItems.splice(targetGlobalIndex, 0, removed);
-
-              setItems(newItems);
-              saveItemOrder(newItems);
-            }
+            onMoveItemRef.current(currentDraggedItem, curPage, targetSlot);
           }
         }
 
@@ -545,21 +471,9 @@ export function useDragAndDrop({
             !elementsAtPoint.find((el) => el.classList.contains("folder-content"))
           ) {
             if (curOpenFolder) {
-              const appToRemove = currentDraggedItem;
-              const updatedFolder = {
-                ...curOpenFolder,
-                apps: curOpenFolder.apps.filter((a) => a.bundle_id !== appToRemove.bundle_id),
-              };
-
-              const newFolders = curFolders.map((f) => (f.id === curOpenFolder.id ? updatedFolder : f));
-              setFolders(newFolders);
-              saveFolders(newFolders);
-
-              if (updatedFolder.apps.length === 0) {
-                setOpenFolder(null);
-              } else {
-                setOpenFolder(updatedFolder);
-              }
+              // Out of the folder to the end of the current page (a folder left
+              // empty goes away and closes)
+              onMoveItemRef.current(currentDraggedItem, curPage, Number.MAX_SAFE_INTEGER);
             }
           }
         }
@@ -1,79 +1,101 @@
-import { useState } from "react";
-import type { Folder } from "../types";
+import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { Folder, LaunchpadItem } from "../types";
+import { isFolder } from "../types";
 
 /**
- * Hook that manages folder CRUD operations and persistence
+ * Hook that manages the open folder and folder operations
+ * The backend layout owns the folders; changes come back through "layout-changed"
  */
-export function useFolderManagement() {
-  const [folders, setFolders] = useState<Folder[]>(() => {
-    const savedFolders = localStorage.getItem("launchpad-folders");
-    if (savedFolders) {
-      try {
-        return JSON.parse(savedFolders);
-      } catch (err) {
-        console.error("Failed to load folders:", err);
-        return [];
-      }
+export function useFolderManagement(items: LaunchpadItem[]) {
+  const [openFolder, setOpenFolderState] = useState<Folder | null>(null);
+
+  // Name typed into the open folder's title, saved when the folder is closed
+  const pendingNameRef = useRef<{ folderId: string; name: string } | null>(null);
+
+  /**
+   * Save a folder name (blank or unchanged names are ignored)
+   */
+  async function renameFolder(folderId: string, name: string) {
+    const folder = items.find((item) => isFolder(item) && item.id === folderId);
+    if (!name.trim() || (folder && folder.name === name)) return;
+
+    try {
+      await invoke("rename_folder", { folderId, name });
+    } catch (err) {
+      console.error("Failed to rename folder:", err);
     }
-    return [];
-  });
-  const [openFolder, setOpenFolder] = useState<Folder | null>(null);
+  }
 
   /**
-   * Save folders to localStorage and update state
+   * Open a folder (or close it with null), saving the name typed into the one closed
    */
-  function saveFolders(newFolders: Folder[]) {
-    localStorage.setItem("launchpad-folders", JSON.stringify(newFolders));
-    setFolders(newFolders);
+  function setOpenFolder(folder: Folder | null) {
+    const pending = pendingNameRef.current;
+    if (pending && pending.folderId !== folder?.id) {
+      pendingNameRef.current = null;
+      renameFolder(pending.folderId, pending.name);
+    }
+    setOpenFolderState(folder);
   }
 
+  // Keep the open folder in step with the layout (a typed name is kept until saved)
+  useEffect(() => {
+    if (!openFolder) return;
+
+    const current = items.find((item) => isFolder(item) && item.id === openFolder.id) as
+      | Folder
+      | undefined;
+    if (!current) {
+      pendingNameRef.current = null;
+      setOpenFolderState(null);
+      return;
+    }
+    const pending = pendingNameRef.current;
+    setOpenFolderState(pending?.folderId === current.id ? { ...current, name: pending.name } : current);
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [items]);
+
   /**
-   * Create a new empty folder
+   * Create a new empty folder at the end of the grid
    */
-  function createFolder() {
-    const newFolder: Folder = {
-      id: `folder-${Date.now()}`,
-      name: "New Folder",
-      apps: [],
-    };
-    const newFolders = [...folders, newFolder];
-    saveFolders(newFolders);
+  async function createFolder(name: string): Promise<Folder | null> {
+    try {
+      const folder = await invoke<{ id: string; name: string }>("create_folder", {
+        name,
+        bundleIds: [],
+      });
+      return { id: folder.id, name: folder.name, apps: [] };
+    } catch (err) {
+      console.error("Failed to create folder:", err);
+      return null;
+    }
   }
 
   /**
    * Delete a folder and return its apps to the main grid
    */
-  function deleteFolder(folderId: string) {
-    const folder = folders.find((f) => f.id === folderId);
-    if (!folder) return;
-
-    // Remove folder from folders list
-    const newFolders = folders.filter((f) => f.id !== folderId);
-    saveFolders(newFolders);
-
-    // Apps will be automatically added back to the grid by the parent component
-    setOpenFolder(null);
+  async function deleteFolder(folderId: string) {
+    pendingNameRef.current = null;
+    setOpenFolderState(null);
+    try {
+      await invoke("delete_folder", { folderId });
+    } catch (err) {
+      console.error("Failed to delete folder:", err);
+    }
   }
 
   /**
-   * Update folder name
+   * Update folder name (saved when the folder is closed)
    */
   function updateFolderName(folderId: string, name: string) {
-    const newFolders = folders.map((f) => (f.id === folderId ? { ...f, name } : f));
-    saveFolders(newFolders);
-
-    // Update open folder if it's the one being renamed
-    if (openFolder && openFolder.id === folderId) {
-      setOpenFolder({ ...openFolder, name });
-    }
+    pendingNameRef.current = { folderId, name };
+    setOpenFolderState((folder) => (folder && folder.id === folderId ? { ...folder, name } : folder));
   }
 
   return {
-    folders,
     openFolder,
-    setFolders,
     setOpenFolder,
-    saveFolders,
     createFolder,
     deleteFolder,
     updateFolderName,
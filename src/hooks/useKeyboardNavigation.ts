@@ -3,8 +3,7 @@ import { getCurrentWindow } from "@tauri-apps/api/window";
 import type { DeleteConfirmationState } from "../types";
 
 interface UseKeyboardNavigationProps {
-  filteredItemsLength: number;
-  appsPerPage: number;
+  totalPages: number;
   setCurrentPage: React.Dispatch<React.SetStateAction<number>>;
   searchQuery: string;
   isAnySettingsOpen: boolean;
@@ -26,8 +25,7 @@ interface UseKeyboardNavigationProps {
  * Handles Escape, Arrow keys, PageUp/PageDown, and mouse wheel
  */
 export function useKeyboardNavigation({
-  filteredItemsLength,
-  appsPerPage,
+  totalPages,
   setCurrentPage,
   searchQuery,
   isAnySettingsOpen,
@@ -88,23 +86,20 @@ export function useKeyboardNavigation({
         return;
       }
 
-      const pages = Math.ceil(filteredItemsLength / appsPerPage);
-
       // Page navigation
       if (e.key === "ArrowLeft" || e.key === "PageUp") {
         e.preventDefault();
         setCurrentPage((prev) => Math.max(0, prev - 1));
       } else if (e.key === "ArrowRight" || e.key === "PageDown") {
         e.preventDefault();
-        setCurrentPage((prev) => Math.min(pages - 1, prev + 1));
+        setCurrentPage((prev) => Math.min(totalPages - 1, prev + 1));
       }
     };
 
     window.addEventListener("keydown", handleKeyDown);
     return () => window.removeEventListener("keydown", handleKeyDown);
   }, [
-    filteredItemsLength,
-    appsPerPage,
+    totalPages,
     isAnySettingsOpen,
     openFolder,
     deleteConfirmation,
@@ -129,8 +124,7 @@ export function useKeyboardNavigation({
       if (searchQuery) return;
       if (isAnySettingsOpen || openFolder) return;
 
-      const pages = Math.ceil(filteredItemsLength / appsPerPage);
-      if (pages <= 1) return;
+      if (totalPages <= 1) return;
 
       const now = Date.now();
       if (now - lastWheelTime < WHEEL_DEBOUNCE) return;
@@ -143,7 +137,7 @@ export function useKeyboardNavigation({
           // Scroll down = next page
           setCurrentPage((prev) => {
             const next = prev + 1;
-            return next < pages ? next : prev;
+            return next < totalPages ? next : prev;
           });
         } else {
           // Scroll up = previous page
@@ -157,5 +151,5 @@ export function useKeyboardNavigation({
 
     window.addEventListener("wheel", handleWheel);
     return () => window.removeEventListener("wheel", handleWheel);
-  }, [filteredItemsLength, appsPerPage, searchQuery, isAnySettingsOpen, openFolder, setCurrentPage]);
+  }, [totalPages, searchQuery, isAnySettingsOpen, openFolder, setCurrentPage]);
 }
@@ -14,6 +14,36 @@ export interface App {
   icon: string | null;
   source_folder?: string;
   tags: string[]; // Auto-detected category tags
+  kind?: "app" | "web-app";
+  web_app?: WebAppInfo | null;
+  developer?: string | null; // App Store artist for iOS apps
+  genre?: string | null; // App Store genre for iOS apps
+  original_name?: string | null; // Bundle's own name when the user renamed the app
+  is_new?: boolean; // Installed recently and not launched since
+  recently_updated?: boolean; // Updated recently and not launched since
+}
+
+/**
+ * Site and host browser of a browser-installed web app
+ */
+export interface WebAppInfo {
+  url: string | null;
+  host_browser: string;
+}
+
+/**
+ * Why launching an app failed, as reported by the backend
+ */
+export interface LaunchError {
+  kind:
+    | "missing-bundle"
+    | "damaged"
+    | "not-permitted"
+    | "incompatible"
+    | "invalid-options"
+    | "unknown";
+  app_path: string;
+  message: string; // Raw error output from `open`
 }
 
 /**
@@ -30,6 +60,31 @@ export interface Folder {
  */
 export type LaunchpadItem = App | Folder;
 
+/**
+ * Reference to an item placed on a page of the backend layout
+ */
+export type LayoutItemRef = { type: "app"; bundle_id: string } | { type: "folder"; id: string };
+
+/**
+ * Folder as the backend layout stores it (apps by bundle ID)
+ */
+export interface LayoutFolder {
+  id: string;
+  name: string;
+  apps: string[];
+}
+
+/**
+ * The persisted arrangement of apps, owned by the backend
+ */
+export interface Layout {
+  version: number;
+  page_size: number;
+  pages: { items: LayoutItemRef[] }[];
+  folders: LayoutFolder[];
+  favorites: string[];
+}
+
 /**
  * Represents a tag for categorizing apps
  */
@@ -77,6 +132,32 @@ export interface GridSettings {
   tagSettings: TagSettings;
 }
 
+/**
+ * Settings as the backend stores them (the backend owns and applies them)
+ */
+export interface BackendSettings {
+  version: number;
+  rows: number;
+  cols: number;
+  full_width: boolean;
+  hot_corner: {
+    enabled: boolean;
+    corner: string;
+    threshold: number;
+    debounce_ms: number;
+  };
+  global_shortcut: string;
+  tags: {
+    show_tag_bar: boolean;
+    auto_tag_new_apps: boolean;
+  };
+  new_app_placement: string;
+  new_apps_folder_name: string;
+  language: string;
+  favorites_position: string;
+  new_badge_days: number;
+}
+
 /**
  * Context menu position
  */
@@ -115,3 +196,10 @@ export interface MouseDownItemState {
 export function isFolder(item: LaunchpadItem): item is Folder {
   return "apps" in item;
 }
+
+/**
+ * Backend layout reference to a grid item
+ */
+export function toItemRef(item: LaunchpadItem): LayoutItemRef {
+  return isFolder(item) ? { type: "folder", id: item.id } : { type: "app", bundle_id: item.bundle_id };
+}